@@ -27,6 +27,12 @@ impl RngCore for TestRng {
 impl CryptoRng for TestRng {}
 
 
+// KeyGen/Encaps/Decaps all bottom out in the NTT/MultiplyNTTs kernel selected by `ntt_backend`
+// (see that module for why it's build-time rather than a runtime parameter, and for why every
+// `Kernel` variant besides `Portable` is currently a reserved placeholder rather than a distinct
+// vectorized implementation). Running this bench under `--features
+// force-portable`/`force-sse2`/`force-avx2`/`force-neon` exercises the dispatch plumbing for each
+// selector, not yet distinct kernel code.
 #[allow(clippy::redundant_closure)]
 pub fn criterion_benchmark(c: &mut Criterion) {
     // Generate intermediate values needed for the actual benchmark functions
@@ -42,6 +42,108 @@ impl RngCore for TestRng {
 impl CryptoRng for TestRng {}
 
 
+/// Online mean/variance accumulator (Welford's algorithm), used for both the first-order
+/// (raw cycle count) and second-order (centered-squared) dudect-style classes below.
+struct Welford {
+    n: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl Welford {
+    const fn new() -> Self { Welford { n: 0, mean: 0.0, m2: 0.0 } }
+
+    fn push(&mut self, x: f64) {
+        self.n += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.n as f64;
+        let delta2 = x - self.mean;
+        self.m2 += delta * delta2;
+    }
+
+    fn variance(&self) -> f64 { if self.n < 2 { 0.0 } else { self.m2 / (self.n as f64 - 1.0) } }
+}
+
+
+/// Minimal Newton-Raphson square root; `no_std` has no `f64::sqrt` (that's a `libm`/`std`
+/// intrinsic), and pulling in a dependency for one `sqrt` call isn't worth it here.
+fn sqrt_f64(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let mut guess = x;
+    for _ in 0..20 {
+        guess = 0.5 * (guess + x / guess);
+    }
+    guess
+}
+
+
+/// Welch's t-statistic between two classes' running mean/variance: `t = (μ_a − μ_b) /
+/// sqrt(s_a²/n_a + s_b²/n_b)`. Returns 0.0 (no evidence of a leak yet) until both classes have
+/// at least two samples.
+fn welch_t(a: &Welford, b: &Welford) -> f64 {
+    if a.n < 2 || b.n < 2 {
+        return 0.0;
+    }
+    let se2 = a.variance() / a.n as f64 + b.variance() / b.n as f64;
+    if se2 <= 0.0 {
+        return 0.0;
+    }
+    (a.mean - b.mean) / sqrt_f64(se2)
+}
+
+
+/// |t| beyond this is dudect's conventional "this is leaking" threshold.
+const LEAK_THRESHOLD: f64 = 4.5;
+
+/// Number of raw measurements collected per calibration window before the crop threshold (an
+/// approximate percentile) is recomputed; keeps the window on the stack (no `alloc` in `no_std`).
+const CROP_WINDOW: usize = 128;
+
+/// Sorted index within a filled [`CROP_WINDOW`] approximating the 95th percentile; measurements
+/// above the resulting threshold are treated as OS/interrupt/cache outliers and dropped rather
+/// than fed into the Welford accumulators, so a single stalled iteration can't swing the t-stat.
+const CROP_PERCENTILE_IDX: usize = CROP_WINDOW * 95 / 100;
+
+/// Rejects measurements above an approximate 95th-percentile threshold, recalibrated from the
+/// last [`CROP_WINDOW`] raw samples.
+struct PercentileCrop {
+    window: [f64; CROP_WINDOW],
+    filled: usize,
+    threshold: f64,
+}
+
+impl PercentileCrop {
+    const fn new() -> Self {
+        PercentileCrop { window: [0.0; CROP_WINDOW], filled: 0, threshold: f64::MAX }
+    }
+
+    /// Records `x` for the next calibration and reports whether it fell under the current
+    /// threshold (and should therefore be kept).
+    fn accept(&mut self, x: f64) -> bool {
+        let keep = x <= self.threshold;
+        self.window[self.filled] = x;
+        self.filled += 1;
+        if self.filled == CROP_WINDOW {
+            let mut sorted = self.window;
+            for i in 1..CROP_WINDOW {
+                let key = sorted[i];
+                let mut j = i;
+                while j > 0 && sorted[j - 1] > key {
+                    sorted[j] = sorted[j - 1];
+                    j -= 1;
+                }
+                sorted[j] = key;
+            }
+            self.threshold = sorted[CROP_PERCENTILE_IDX];
+            self.filled = 0;
+        }
+        keep
+    }
+}
+
+
 #[entry]
 fn main() -> ! {
     let mut board = Board::take().unwrap();
@@ -50,9 +152,19 @@ fn main() -> ! {
     board.display_pins.col1.set_low().unwrap();
     rtt_init_print!();
 
-    let mut rng = TestRng { rho: 999, value: 4 }; // arbitrary choice (value must be mult of 4)
-    let mut spare_draw = [0u8; 32];
-    let mut expected_cycles = 0;
+    // Class A (fixed): reconstructed identically every time it runs, so keygen/encaps/decaps
+    // always operate over the same dk/ct pair. Class B (random): the live, ever-advancing rng,
+    // so every run uses fresh coins. Interleaved per-iteration below to cancel timing drift.
+    let fixed_rng = TestRng { rho: 999, value: 4 };
+    let mut random_rng = TestRng { rho: 999, value: 4 };
+
+    let mut crop = PercentileCrop::new();
+    let mut class_a = Welford::new();
+    let mut class_b = Welford::new();
+    let mut combined = Welford::new();
+    let mut class_a_2nd = Welford::new();
+    let mut class_b_2nd = Welford::new();
+
     let mut i = 0u32;
 
     loop {
@@ -62,7 +174,9 @@ fn main() -> ! {
         if (i % 100) == 50 {
             board.display_pins.row1.set_low().unwrap();
         };
-        i += 1;
+
+        let is_class_a = (i & 1) == 0;
+        let mut rng = if is_class_a { fixed_rng.clone() } else { random_rng.clone() };
 
         ///////////////////// Start measurement period
         asm::isb();
@@ -79,23 +193,40 @@ fn main() -> ! {
         asm::isb();
         ///////////////////// Finish measurement period
 
-        let _ = rng.try_fill_bytes(&mut spare_draw).unwrap(); // ease our lives; multiple of 4
-        let count = finish - start;
+        if !is_class_a {
+            random_rng = rng; // only class B's rng advances, keeping class A's input fixed
+        }
+
+        let count = f64::from(finish - start);
+        if crop.accept(count) {
+            combined.push(count);
+            let centered_sq = (count - combined.mean) * (count - combined.mean);
+            if is_class_a {
+                class_a.push(count);
+                class_a_2nd.push(centered_sq);
+            } else {
+                class_b.push(count);
+                class_b_2nd.push(centered_sq);
+            }
+        }
 
-        // each rho should have a fixed cycle count
-        if (i % 1000) == 0 {
-            rng.rho += 1
-        };
-        // capture the cycle count
-        if (i % 1000) == 2 {
-            expected_cycles = count
-        };
-        // make sure it is constant
-        if ((i % 1000) > 2) & (count != expected_cycles) {
-            panic!("Non constant-time operation!! iteration:{} cycles:{}", i, count)
-        };
         if i % 100 == 0 {
-            rprintln!("Iteration {} cycle count: {}", i, count)
-        };
+            let t1 = welch_t(&class_a, &class_b);
+            let t2 = welch_t(&class_a_2nd, &class_b_2nd);
+            rprintln!(
+                "Iteration {} cycles:{} t(1st order):{} t(2nd order):{}{}",
+                i,
+                count,
+                t1,
+                t2,
+                if t1.abs() > LEAK_THRESHOLD || t2.abs() > LEAK_THRESHOLD {
+                    " <- possible leak"
+                } else {
+                    ""
+                }
+            );
+        }
+
+        i = i.wrapping_add(1);
     }
 }
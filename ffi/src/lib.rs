@@ -46,6 +46,53 @@ pub extern "C" fn ml_kem_512_keygen(
     return ML_KEM_OK;
 }
 
+#[cfg(feature = "deterministic")]
+#[no_mangle]
+pub extern "C" fn ml_kem_512_keygen_from_seed(
+    d: Option<&[u8; 32]>, z: Option<&[u8; 32]>, encaps_out: Option<&mut ml_kem_512_encaps_key>,
+    decaps_out: Option<&mut ml_kem_512_decaps_key>,
+) -> u8 {
+    use fips203::traits::{KeyGen, SerDes};
+
+    let (Some(d), Some(z), Some(encaps_out), Some(decaps_out)) = (d, z, encaps_out, decaps_out)
+    else {
+        return ML_KEM_NULL_PTR_ERROR;
+    };
+    let Ok((ek, dk)) = fips203::ml_kem_512::KG::keygen_internal(d, z) else {
+        return ML_KEM_KEYGEN_ERROR;
+    };
+
+    encaps_out.data = ek.into_bytes();
+    decaps_out.data = dk.into_bytes();
+    return ML_KEM_OK;
+}
+
+#[cfg(feature = "deterministic")]
+#[no_mangle]
+pub extern "C" fn ml_kem_512_encaps_from_seed(
+    encaps: Option<&ml_kem_512_encaps_key>, m: Option<&[u8; 32]>,
+    ciphertext_out: Option<&mut ml_kem_512_ciphertext>,
+    shared_secret_out: Option<&mut ml_kem_shared_secret>,
+) -> u8 {
+    use fips203::traits::{Encaps, SerDes};
+
+    let (Some(encaps), Some(m), Some(ciphertext_out), Some(shared_secret_out)) =
+        (encaps, m, ciphertext_out, shared_secret_out)
+    else {
+        return ML_KEM_NULL_PTR_ERROR;
+    };
+    let Ok(ek) = fips203::ml_kem_512::EncapsKey::try_from_bytes(encaps.data) else {
+        return ML_KEM_DESERIALIZATION_ERROR;
+    };
+    let Ok((ssk, ct)) = ek.encaps_deterministic(m) else {
+        return ML_KEM_ENCAPSULATION_ERROR;
+    };
+
+    shared_secret_out.data = ssk.into_bytes();
+    ciphertext_out.data = ct.into_bytes();
+    return ML_KEM_OK;
+}
+
 #[no_mangle]
 pub extern "C" fn ml_kem_512_encaps(
     encaps: Option<&ml_kem_512_encaps_key>, ciphertext_out: Option<&mut ml_kem_512_ciphertext>,
@@ -129,6 +176,53 @@ pub extern "C" fn ml_kem_768_keygen(
     return ML_KEM_OK;
 }
 
+#[cfg(feature = "deterministic")]
+#[no_mangle]
+pub extern "C" fn ml_kem_768_keygen_from_seed(
+    d: Option<&[u8; 32]>, z: Option<&[u8; 32]>, encaps_out: Option<&mut ml_kem_768_encaps_key>,
+    decaps_out: Option<&mut ml_kem_768_decaps_key>,
+) -> u8 {
+    use fips203::traits::{KeyGen, SerDes};
+
+    let (Some(d), Some(z), Some(encaps_out), Some(decaps_out)) = (d, z, encaps_out, decaps_out)
+    else {
+        return ML_KEM_NULL_PTR_ERROR;
+    };
+    let Ok((ek, dk)) = fips203::ml_kem_768::KG::keygen_internal(d, z) else {
+        return ML_KEM_KEYGEN_ERROR;
+    };
+
+    encaps_out.data = ek.into_bytes();
+    decaps_out.data = dk.into_bytes();
+    return ML_KEM_OK;
+}
+
+#[cfg(feature = "deterministic")]
+#[no_mangle]
+pub extern "C" fn ml_kem_768_encaps_from_seed(
+    encaps: Option<&ml_kem_768_encaps_key>, m: Option<&[u8; 32]>,
+    ciphertext_out: Option<&mut ml_kem_768_ciphertext>,
+    shared_secret_out: Option<&mut ml_kem_shared_secret>,
+) -> u8 {
+    use fips203::traits::{Encaps, SerDes};
+
+    let (Some(encaps), Some(m), Some(ciphertext_out), Some(shared_secret_out)) =
+        (encaps, m, ciphertext_out, shared_secret_out)
+    else {
+        return ML_KEM_NULL_PTR_ERROR;
+    };
+    let Ok(ek) = fips203::ml_kem_768::EncapsKey::try_from_bytes(encaps.data) else {
+        return ML_KEM_DESERIALIZATION_ERROR;
+    };
+    let Ok((ssk, ct)) = ek.encaps_deterministic(m) else {
+        return ML_KEM_ENCAPSULATION_ERROR;
+    };
+
+    shared_secret_out.data = ssk.into_bytes();
+    ciphertext_out.data = ct.into_bytes();
+    return ML_KEM_OK;
+}
+
 #[no_mangle]
 pub extern "C" fn ml_kem_768_encaps(
     encaps: Option<&ml_kem_768_encaps_key>, ciphertext_out: Option<&mut ml_kem_768_ciphertext>,
@@ -213,6 +307,53 @@ pub extern "C" fn ml_kem_1024_keygen(
     return ML_KEM_OK;
 }
 
+#[cfg(feature = "deterministic")]
+#[no_mangle]
+pub extern "C" fn ml_kem_1024_keygen_from_seed(
+    d: Option<&[u8; 32]>, z: Option<&[u8; 32]>, encaps_out: Option<&mut ml_kem_1024_encaps_key>,
+    decaps_out: Option<&mut ml_kem_1024_decaps_key>,
+) -> u8 {
+    use fips203::traits::{KeyGen, SerDes};
+
+    let (Some(d), Some(z), Some(encaps_out), Some(decaps_out)) = (d, z, encaps_out, decaps_out)
+    else {
+        return ML_KEM_NULL_PTR_ERROR;
+    };
+    let Ok((ek, dk)) = fips203::ml_kem_1024::KG::keygen_internal(d, z) else {
+        return ML_KEM_KEYGEN_ERROR;
+    };
+
+    encaps_out.data = ek.into_bytes();
+    decaps_out.data = dk.into_bytes();
+    return ML_KEM_OK;
+}
+
+#[cfg(feature = "deterministic")]
+#[no_mangle]
+pub extern "C" fn ml_kem_1024_encaps_from_seed(
+    encaps: Option<&ml_kem_1024_encaps_key>, m: Option<&[u8; 32]>,
+    ciphertext_out: Option<&mut ml_kem_1024_ciphertext>,
+    shared_secret_out: Option<&mut ml_kem_shared_secret>,
+) -> u8 {
+    use fips203::traits::{Encaps, SerDes};
+
+    let (Some(encaps), Some(m), Some(ciphertext_out), Some(shared_secret_out)) =
+        (encaps, m, ciphertext_out, shared_secret_out)
+    else {
+        return ML_KEM_NULL_PTR_ERROR;
+    };
+    let Ok(ek) = fips203::ml_kem_1024::EncapsKey::try_from_bytes(encaps.data) else {
+        return ML_KEM_DESERIALIZATION_ERROR;
+    };
+    let Ok((ssk, ct)) = ek.encaps_deterministic(m) else {
+        return ML_KEM_ENCAPSULATION_ERROR;
+    };
+
+    shared_secret_out.data = ssk.into_bytes();
+    ciphertext_out.data = ct.into_bytes();
+    return ML_KEM_OK;
+}
+
 #[no_mangle]
 pub extern "C" fn ml_kem_1024_encaps(
     encaps: Option<&ml_kem_1024_encaps_key>, ciphertext_out: Option<&mut ml_kem_1024_ciphertext>,
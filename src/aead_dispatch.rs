@@ -0,0 +1,73 @@
+//! The `AeadAlg` selector and low-level seal/open dispatch shared by [`crate::seal`] and
+//! [`crate::hpke`]: both layer AES-256-GCM or `ChaCha20Poly1305` on top of an already-derived
+//! 32-byte key and 12-byte nonce, differing only in how they derive that key upstream (one
+//! SHAKE256 squeeze for [`crate::seal`], RFC 9180's HKDF-SHA256 schedule for [`crate::hpke`]) and
+//! in their error-message text. Lives here, gated on either module being enabled, so the enum and
+//! its match arms can't drift apart as AEAD algorithms are added or messages are tuned in just
+//! one of the two; each caller maps the resulting [`AeadError`] onto its own prefixed
+//! `&'static str` with a one-line match instead of repeating the whole dispatch.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use aead::{Aead as _, KeyInit as _, Payload};
+
+/// The AEAD layered on top of a KEM- or XOF/HKDF-derived key. Re-exported as `AeadAlg` by both
+/// [`crate::seal`] and [`crate::hpke`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AeadAlg {
+    /// AES-256 in Galois/Counter Mode.
+    Aes256Gcm,
+    /// `ChaCha20Poly1305`.
+    ChaCha20Poly1305,
+}
+
+/// What can go wrong in [`AeadAlg::seal`]/[`AeadAlg::open`]; callers turn this into their own
+/// prefixed `&'static str` (e.g. `"seal: ..."` or `"HPKE: ..."`).
+pub(crate) enum AeadError {
+    /// `key` was the wrong length for `AeadAlg`. Unreachable in practice given this module's own
+    /// fixed-size `[u8; 32]` signature, but the underlying AEAD crates return a `Result` here
+    /// rather than taking a const-generic-sized array.
+    BadKeyLength(AeadAlg),
+    /// AEAD encryption failed.
+    SealFailed,
+    /// AEAD decryption or tag verification failed.
+    OpenFailed,
+}
+
+impl AeadAlg {
+    /// Encrypts `pt`, authenticating `aad`, under `key`/`nonce`.
+    pub(crate) fn seal(
+        self, key: &[u8; 32], nonce: &[u8; 12], aad: &[u8], pt: &[u8],
+    ) -> Result<Vec<u8>, AeadError> {
+        let payload = Payload { msg: pt, aad };
+        match self {
+            Self::Aes256Gcm => aes_gcm::Aes256Gcm::new_from_slice(key)
+                .map_err(|_| AeadError::BadKeyLength(self))?
+                .encrypt(aes_gcm::Nonce::from_slice(nonce), payload)
+                .map_err(|_| AeadError::SealFailed),
+            Self::ChaCha20Poly1305 => chacha20poly1305::ChaCha20Poly1305::new_from_slice(key)
+                .map_err(|_| AeadError::BadKeyLength(self))?
+                .encrypt(chacha20poly1305::Nonce::from_slice(nonce), payload)
+                .map_err(|_| AeadError::SealFailed),
+        }
+    }
+
+    /// Reverses [`Self::seal`]: decrypts `ct`, checking `aad`, under `key`/`nonce`.
+    pub(crate) fn open(
+        self, key: &[u8; 32], nonce: &[u8; 12], aad: &[u8], ct: &[u8],
+    ) -> Result<Vec<u8>, AeadError> {
+        let payload = Payload { msg: ct, aad };
+        match self {
+            Self::Aes256Gcm => aes_gcm::Aes256Gcm::new_from_slice(key)
+                .map_err(|_| AeadError::BadKeyLength(self))?
+                .decrypt(aes_gcm::Nonce::from_slice(nonce), payload)
+                .map_err(|_| AeadError::OpenFailed),
+            Self::ChaCha20Poly1305 => chacha20poly1305::ChaCha20Poly1305::new_from_slice(key)
+                .map_err(|_| AeadError::BadKeyLength(self))?
+                .decrypt(chacha20poly1305::Nonce::from_slice(nonce), payload)
+                .map_err(|_| AeadError::OpenFailed),
+        }
+    }
+}
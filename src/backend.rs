@@ -0,0 +1,72 @@
+//! Pluggable Keccak/SHA3 permutation backend.
+//!
+//! [`prf`](crate::helpers), `xof`, `g`, `h`, and `j` (the symmetric primitives of FIPS 203 §4) are
+//! generic over [`Sha3Backend`] rather than hard-coding the `sha3` crate's software Keccak-f\[1600\].
+//! [`DefaultSha3Backend`] is that software implementation, and is what every `ml_kem_512`/`768`/
+//! `1024` module uses unless told otherwise. Downstream users on platforms with a hardware-
+//! accelerated or formally-verified Keccak core (e.g. the ARM64 SHA3 crypto extensions, or an
+//! AVX2 batched permutation) can implement this trait over their own core and substitute it —
+//! this is exactly how accelerated PQC stacks separate the SHA3 core from the scheme logic.
+
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Digest, Sha3_256, Sha3_512, Shake128, Shake256};
+
+
+/// A Keccak/SHA3 permutation backend supplying the four primitives FIPS 203 builds its symmetric
+/// functions from: `SHAKE128`, `SHAKE256`, `SHA3-256`, and `SHA3-512`. Every method takes its
+/// input as a list of byte slices rather than one concatenated buffer, matching how `g`/`xof`/`j`
+/// already avoid an up-front concatenation allocation.
+pub trait Sha3Backend {
+    /// Reader type returned by [`Sha3Backend::shake128`].
+    type Shake128Reader: XofReader;
+    /// Reader type returned by [`Sha3Backend::shake256`].
+    type Shake256Reader: XofReader;
+
+    /// Absorbs `data` (in order) into a fresh `SHAKE128` state and returns its XOF reader.
+    fn shake128(data: &[&[u8]]) -> Self::Shake128Reader;
+
+    /// Absorbs `data` (in order) into a fresh `SHAKE256` state and returns its XOF reader.
+    fn shake256(data: &[&[u8]]) -> Self::Shake256Reader;
+
+    /// Hashes `data` (in order) with `SHA3-256`.
+    fn sha3_256(data: &[&[u8]]) -> [u8; 32];
+
+    /// Hashes `data` (in order) with `SHA3-512`.
+    fn sha3_512(data: &[&[u8]]) -> [u8; 64];
+}
+
+
+/// The default [`Sha3Backend`]: the `sha3` crate's software Keccak-f\[1600\]. Used by every
+/// `ml_kem_512`/`768`/`1024` module unless a parameter-set module is edited to name a different
+/// [`Sha3Backend`] implementation in its place.
+#[derive(Clone, Copy, Default)]
+pub struct DefaultSha3Backend;
+
+impl Sha3Backend for DefaultSha3Backend {
+    type Shake128Reader = <Shake128 as ExtendableOutput>::Reader;
+    type Shake256Reader = <Shake256 as ExtendableOutput>::Reader;
+
+    fn shake128(data: &[&[u8]]) -> Self::Shake128Reader {
+        let mut hasher = Shake128::default();
+        data.iter().for_each(|d| hasher.update(d));
+        hasher.finalize_xof()
+    }
+
+    fn shake256(data: &[&[u8]]) -> Self::Shake256Reader {
+        let mut hasher = Shake256::default();
+        data.iter().for_each(|d| hasher.update(d));
+        hasher.finalize_xof()
+    }
+
+    fn sha3_256(data: &[&[u8]]) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        data.iter().for_each(|d| Digest::update(&mut hasher, d));
+        hasher.finalize().into()
+    }
+
+    fn sha3_512(data: &[&[u8]]) -> [u8; 64] {
+        let mut hasher = Sha3_512::new();
+        data.iter().for_each(|d| Digest::update(&mut hasher, d));
+        hasher.finalize().into()
+    }
+}
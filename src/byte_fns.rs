@@ -33,6 +33,24 @@ pub(crate) fn byte_encode(d: u32, integers_f: &[Z; 256], bytes_b: &mut [u8]) {
             .all(|f| f.get_u16() <= if d < 12 { 1 << d } else { Q }),
         "Alg 4: integers_f out of range"
     );
+
+    // The six `d` values FIPS 203 actually instantiates (1, 4, 5, 10, 11, 12 — see table 2 and the
+    // ek/dk/ciphertext encodings) get a chunked path: unlike the generic loop below, whose `temp`/
+    // `bit_index` carry a serial dependency across all 256 coefficients, each chunk here is encoded
+    // independently into its own register with no cross-chunk dependency, which is what actually
+    // lets the compiler pipeline or auto-vectorize the hot path rather than a true SIMD intrinsic
+    // (barred by `#![deny(unsafe_code)]`). Falls back to the scalar loop for any other `d`.
+    #[cfg(feature = "vectorized-codec")]
+    match d {
+        1 => return byte_encode_chunked::<1, 8, 1>(integers_f, bytes_b),
+        4 => return byte_encode_chunked::<4, 2, 1>(integers_f, bytes_b),
+        5 => return byte_encode_chunked::<5, 8, 5>(integers_f, bytes_b),
+        10 => return byte_encode_chunked::<10, 4, 5>(integers_f, bytes_b),
+        11 => return byte_encode_chunked::<11, 8, 11>(integers_f, bytes_b),
+        12 => return byte_encode_chunked::<12, 2, 3>(integers_f, bytes_b),
+        _ => (),
+    }
+
     //
     // Our "working" register, from which to drop bytes out of
     let mut temp = 0u32;
@@ -65,6 +83,26 @@ pub(crate) fn byte_encode(d: u32, integers_f: &[Z; 256], bytes_b: &mut [u8]) {
 }
 
 
+/// Chunked encode for one of the fixed `D`-bit widths FIPS 203 uses. `CHUNK_COEFFS` and
+/// `CHUNK_BYTES` are `lcm(D, 8) / D` and `lcm(D, 8) / 8`, i.e. the smallest number of coefficients
+/// that packs into a whole number of bytes with nothing left over, so every chunk starts and ends
+/// on a byte boundary and no state needs to carry over to the next chunk. See [`byte_encode`].
+#[cfg(feature = "vectorized-codec")]
+fn byte_encode_chunked<const D: u32, const CHUNK_COEFFS: usize, const CHUNK_BYTES: usize>(
+    integers_f: &[Z; 256], bytes_b: &mut [u8],
+) {
+    for (f_chunk, b_chunk) in integers_f.chunks_exact(CHUNK_COEFFS).zip(bytes_b.chunks_exact_mut(CHUNK_BYTES)) {
+        let mut temp = 0u128;
+        let mut bit_index = 0;
+        for coeff in f_chunk {
+            temp |= u128::from(coeff.get_u32() & ((1 << D) - 1)) << bit_index;
+            bit_index += D as usize;
+        }
+        b_chunk.copy_from_slice(&temp.to_le_bytes()[..CHUNK_BYTES]);
+    }
+}
+
+
 /// Algorithm 5 `ByteDecode_d(B)` on page 19.
 /// Decodes a byte array into an array of d-bit integers, for 1 ≤ d ≤ 12.
 /// This is an optimized variant (which does not use individual bit functions).
@@ -75,6 +113,55 @@ pub(crate) fn byte_decode(
     d: u32, bytes_b: &[u8], integers_f: &mut [Z; 256],
 ) -> Result<(), &'static str> {
     debug_assert_eq!(bytes_b.len(), 32 * d as usize, "Alg 5: bytes len is not 32 * d");
+
+    // See the matching dispatch in `byte_encode` for why these six `d` values get a chunked,
+    // cross-chunk-independent path instead of the single serial `temp`/`bit_index` carry below.
+    #[cfg(feature = "vectorized-codec")]
+    match d {
+        1 => byte_decode_chunked::<1, 8, 1>(bytes_b, integers_f),
+        4 => byte_decode_chunked::<4, 2, 1>(bytes_b, integers_f),
+        5 => byte_decode_chunked::<5, 8, 5>(bytes_b, integers_f),
+        10 => byte_decode_chunked::<10, 4, 5>(bytes_b, integers_f),
+        11 => byte_decode_chunked::<11, 8, 11>(bytes_b, integers_f),
+        12 => byte_decode_chunked::<12, 2, 3>(bytes_b, integers_f),
+        _ => byte_decode_scalar(d, bytes_b, integers_f),
+    }
+    #[cfg(not(feature = "vectorized-codec"))]
+    byte_decode_scalar(d, bytes_b, integers_f);
+
+    // Supports modulus check per FIPS 203 section 6.2.2
+    let m = if d < 12 { 1 << d } else { u32::from(Q) };
+    ensure!(integers_f.iter().all(|e| e.get_u32() < m), "Alg 5: integers out of range");
+    Ok(())
+}
+
+
+/// Chunked decode for one of the fixed `D`-bit widths FIPS 203 uses; see [`byte_encode_chunked`]
+/// for the `CHUNK_COEFFS`/`CHUNK_BYTES` byte-boundary reasoning.
+#[cfg(feature = "vectorized-codec")]
+#[allow(clippy::cast_possible_truncation)] // Intentional truncation, temp as u16
+fn byte_decode_chunked<const D: u32, const CHUNK_COEFFS: usize, const CHUNK_BYTES: usize>(
+    bytes_b: &[u8], integers_f: &mut [Z; 256],
+) {
+    for (b_chunk, f_chunk) in bytes_b.chunks_exact(CHUNK_BYTES).zip(integers_f.chunks_exact_mut(CHUNK_COEFFS)) {
+        let mut temp = 0u128;
+        for (i, byte) in b_chunk.iter().enumerate() {
+            temp |= u128::from(*byte) << (8 * i);
+        }
+        for coeff in f_chunk {
+            let mut z = Z::default();
+            z.set_u16((temp & ((1 << D) - 1)) as u16);
+            *coeff = z;
+            temp >>= D;
+        }
+    }
+}
+
+
+/// Generic scalar `ByteDecode_d`, used directly when the `vectorized-codec` feature is off and
+/// as the fallback for any `d` outside the six chunked widths above.
+#[allow(clippy::cast_possible_truncation)] // Intentional truncation, temp as u16
+fn byte_decode_scalar(d: u32, bytes_b: &[u8], integers_f: &mut [Z; 256]) {
     //
     // Our "working" register
     let mut temp = 0u32;
@@ -104,11 +191,6 @@ pub(crate) fn byte_decode(
             int_index += 1;
         }
     }
-
-    // Supports modulus check per FIPS 203 section 6.2.2
-    let m = if d < 12 { 1 << d } else { u32::from(Q) };
-    ensure!(integers_f.iter().all(|e| e.get_u32() < m), "Alg 5: integers out of range");
-    Ok(())
 }
 
 
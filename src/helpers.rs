@@ -1,8 +1,8 @@
+use crate::backend::Sha3Backend;
 use crate::ntt::multiply_ntts;
-use crate::types::Z;
+use crate::types::{PolyVec, Z};
 use crate::Q;
-use sha3::digest::{ExtendableOutput, Update, XofReader};
-use sha3::{Digest, Sha3_256, Sha3_512, Shake128, Shake256};
+use sha3::digest::XofReader;
 
 
 /// If the condition is not met, return an error message. Borrowed from the `anyhow` crate.
@@ -19,9 +19,7 @@ pub(crate) use ensure; // make available throughout crate
 
 /// Vector addition; See bottom of page 9, second row: `z_hat` = `u_hat` + `v_hat`
 #[must_use]
-pub(crate) fn add_vecs<const K: usize>(
-    vec_a: &[[Z; 256]; K], vec_b: &[[Z; 256]; K],
-) -> [[Z; 256]; K] {
+pub(crate) fn add_vecs<const K: usize>(vec_a: &PolyVec<K>, vec_b: &PolyVec<K>) -> PolyVec<K> {
     let mut result = [[Z::default(); 256]; K];
     for i in 0..K {
         for n in 0..256 {
@@ -32,81 +30,88 @@ pub(crate) fn add_vecs<const K: usize>(
 }
 
 
-/// Matrix by vector multiplication; See top of page 10, first row: `w_hat` = `A_hat` mul `u_hat`
+/// The `K` terms summed per output coefficient by [`mul_mat_vec`]/[`mul_mat_t_vec`]/
+/// [`dot_t_prod`] are each already-reduced [`Z`] values (`multiply_ntts`'s output), so their sum
+/// is `< K * Q <= 4 * Q` and fits comfortably in `u32` without reducing in between -- see
+/// [`Z::reduce_wide`]. This replaces a per-`j`, branchless-but-still-taken `Z::add` reduction
+/// with a single one per coefficient at the end of the row/column.
 #[must_use]
-pub(crate) fn mul_mat_vec<const K: usize>(
-    a_hat: &[[[Z; 256]; K]; K], u_hat: &[[Z; 256]; K],
-) -> [[Z; 256]; K] {
-    let mut w_hat = [[Z::default(); 256]; K];
-    for i in 0..K {
-        #[allow(clippy::needless_range_loop)] // alternative is harder to understand
-        for j in 0..K {
-            let tmp = multiply_ntts(&a_hat[i][j], &u_hat[j]);
-            for n in 0..256 {
-                w_hat[i][n] = w_hat[i][n].add(tmp[n]);
-            }
+fn sum_row<const K: usize>(row: &PolyVec<K>) -> [Z; 256] {
+    let mut acc = [0u32; 256];
+    for term in row {
+        for n in 0..256 {
+            acc[n] += term[n].get_u32();
         }
     }
-    w_hat
+    core::array::from_fn(|n| Z::reduce_wide(acc[n]))
+}
+
+
+/// Matrix by vector multiplication; See top of page 10, first row: `w_hat` = `A_hat` mul `u_hat`
+#[must_use]
+pub(crate) fn mul_mat_vec<const K: usize>(
+    a_hat: &[PolyVec<K>; K], u_hat: &PolyVec<K>,
+) -> PolyVec<K> {
+    core::array::from_fn(|i| {
+        let row: PolyVec<K> = core::array::from_fn(|j| multiply_ntts(&a_hat[i][j], &u_hat[j]));
+        sum_row(&row)
+    })
 }
 
 
 /// Matrix transpose by vector multiplication; See top of page 10, second row: `y_hat` = `A_hat^T` mul `u_hat`
 #[must_use]
 pub(crate) fn mul_mat_t_vec<const K: usize>(
-    a_hat: &[[[Z; 256]; K]; K], u_hat: &[[Z; 256]; K],
-) -> [[Z; 256]; K] {
-    let mut y_hat = [[Z::default(); 256]; K];
-    #[allow(clippy::needless_range_loop)] // alternative is harder to understand
-    for i in 0..K {
-        #[allow(clippy::needless_range_loop)] // alternative is harder to understand
-        for j in 0..K {
-            let tmp = multiply_ntts(&a_hat[j][i], &u_hat[j]);
-            for n in 0..256 {
-                y_hat[i][n] = y_hat[i][n].add(tmp[n]);
-            }
-        }
-    }
-    y_hat
+    a_hat: &[PolyVec<K>; K], u_hat: &PolyVec<K>,
+) -> PolyVec<K> {
+    core::array::from_fn(|i| {
+        let row: PolyVec<K> = core::array::from_fn(|j| multiply_ntts(&a_hat[j][i], &u_hat[j]));
+        sum_row(&row)
+    })
 }
 
 
 /// Vector dot product; See top of page 10, third row: `z_hat` = `u_hat^T` mul `v_hat`
 #[must_use]
-pub(crate) fn dot_t_prod<const K: usize>(u_hat: &[[Z; 256]; K], v_hat: &[[Z; 256]; K]) -> [Z; 256] {
-    let mut result = [Z::default(); 256];
-    for j in 0..K {
-        let tmp = multiply_ntts(&u_hat[j], &v_hat[j]);
-        for n in 0..256 {
-            result[n] = result[n].add(tmp[n]);
-        }
-    }
-    result
+pub(crate) fn dot_t_prod<const K: usize>(u_hat: &PolyVec<K>, v_hat: &PolyVec<K>) -> [Z; 256] {
+    let row: PolyVec<K> = core::array::from_fn(|j| multiply_ntts(&u_hat[j], &v_hat[j]));
+    sum_row(&row)
 }
 
 
-/// Function PRF on page 16 (4.1).
+/// Function PRF on page 16 (4.1). Generic over [`Sha3Backend`] `B`; pass
+/// [`DefaultSha3Backend`](crate::backend::DefaultSha3Backend) for the standard software Keccak.
 #[must_use]
-pub(crate) fn prf<const ETA_64: usize>(s: &[u8; 32], b: u8) -> [u8; ETA_64] {
-    let mut hasher = Shake256::default();
-    hasher.update(s);
-    hasher.update(&[b]);
-    let mut reader = hasher.finalize_xof();
+pub(crate) fn prf<const ETA_64: usize, B: Sha3Backend>(s: &[u8; 32], b: u8) -> [u8; ETA_64] {
+    let mut reader = B::shake256(&[s, &[b]]);
     let mut result = [0u8; ETA_64];
     reader.read(&mut result);
     result
 }
 
 
-/// Function XOF on page 16 (4.2), used with 32-byte `rho`
+/// Function XOF on page 16 (4.2), used with 32-byte `rho`. Generic over [`Sha3Backend`] `B`.
 #[must_use]
-pub(crate) fn xof(rho: &[u8; 32], i: u8, j: u8) -> impl XofReader {
+pub(crate) fn xof<B: Sha3Backend>(rho: &[u8; 32], i: u8, j: u8) -> B::Shake128Reader {
     //debug_assert_eq!(rho.len(), 32);
-    let mut hasher = Shake128::default();
-    hasher.update(rho);
-    hasher.update(&[i]);
-    hasher.update(&[j]);
-    hasher.finalize_xof()
+    B::shake128(&[rho, &[i], &[j]])
+}
+
+
+/// Batched variant of [`xof`] used to fill one row of `A_hat` at a time: builds `N` independent
+/// SHAKE128 absorb states together rather than one after another. Genuine SIMD Keccak-f\[1600\]
+/// (packing `N` states into one `u64xN` register so theta/rho/pi/chi/iota run identically across
+/// all lanes) is inherently a `target_feature`/`unsafe` technique, which `#![deny(unsafe_code)]`
+/// rules out here; instead this interleaves the `N` *independent* absorb-then-squeeze calls in a
+/// single loop so the compiler is free to pipeline or auto-vectorize across lanes, with the two
+/// domain-separation bytes `i, j` as the only per-lane difference. Gated behind the
+/// `batched-xof` feature; callers without it fall back to calling [`xof`] once per `(i, j)`.
+#[cfg(feature = "batched-xof")]
+#[must_use]
+pub(crate) fn xof_batch<const N: usize, B: Sha3Backend>(
+    rho: &[u8; 32], ij: [(u8, u8); N],
+) -> [B::Shake128Reader; N] {
+    core::array::from_fn(|n| B::shake128(&[rho, &[ij[n].0], &[ij[n].1]]))
 }
 
 
@@ -114,10 +119,9 @@ pub(crate) fn xof(rho: &[u8; 32], i: u8, j: u8) -> impl XofReader {
 /// `g()` is utilized in several different fashions: on a single array as well
 /// as on two concatenated arrays. The single signature here has sufficient
 /// flexibility for reuse and avoiding an unnecessary prior concatenation.
-pub(crate) fn g(bytes: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
-    let mut hasher = Sha3_512::new();
-    bytes.iter().for_each(|b| Digest::update(&mut hasher, b));
-    let digest = hasher.finalize();
+/// Generic over [`Sha3Backend`] `B`.
+pub(crate) fn g<B: Sha3Backend>(bytes: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let digest = B::sha3_512(bytes);
     let a = digest[0..32].try_into().expect("g_a fail");
     let b = digest[32..64].try_into().expect("g_b fail");
     (a, b)
@@ -126,38 +130,65 @@ pub(crate) fn g(bytes: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
 
 /// Function H on page 17 (4.3). <br>
 /// `h()` is used on a variable-length ek, so the signature here is a slice.
+/// Generic over [`Sha3Backend`] `B`.
 #[must_use]
-pub(crate) fn h(bytes: &[u8]) -> [u8; 32] {
-    let mut hasher = Sha3_256::new();
-    Digest::update(&mut hasher, bytes);
-    let digest = hasher.finalize();
-    digest.into()
+pub(crate) fn h<B: Sha3Backend>(bytes: &[u8]) -> [u8; 32] {
+    B::sha3_256(&[bytes])
 }
 
 
 /// Function J n page 17 (4.4). <br>
 /// `j()` is similar to `g()` above in that the second operand is a variable
-/// length `ct`. The signature here is for ease of use.
+/// length `ct`. The signature here is for ease of use. Generic over [`Sha3Backend`] `B`.
 #[must_use]
-pub(crate) fn j(z: &[u8; 32], ct: &[u8]) -> [u8; 32] {
-    let mut hasher = Shake256::default();
-    hasher.update(z);
-    hasher.update(ct);
-    let mut reader = hasher.finalize_xof();
+pub(crate) fn j<B: Sha3Backend>(z: &[u8; 32], ct: &[u8]) -> [u8; 32] {
+    let mut reader = B::shake256(&[z, ct]);
     let mut result = [0u8; 32];
     reader.read(&mut result);
     result
 }
 
 
+/// Number of coefficients processed per chunk by the `simd-compress` path; matches the 8-to-16
+/// packed 32-bit lane width of a typical SIMD register.
+#[cfg(feature = "simd-compress")]
+const SIMD_LANES: usize = 8;
+
+
 /// Compress<d> from page 18 (4.5).
 /// x → ⌈(2^d/q) · x⌋
 /// `d` comes from fixed security parameter, `inout` saves some allocation.
 /// This works for all odd q = 17 to 6307, d = 0 to 11, and x = 0 to q-1.
+#[cfg(not(feature = "simd-compress"))]
+#[allow(clippy::cast_possible_truncation)] // last line (and const)
+pub(crate) fn compress_vector(d: u32, inout: &mut [Z]) {
+    compress_chunk(d, inout);
+}
+
+
+/// `Compress_d`, `simd-compress` variant: applies the same `(x<<d + q/2)·M>>36` recurrence
+/// [`SIMD_LANES`] coefficients at a time instead of one at a time. Each chunk is computed with no
+/// dependency on any other chunk, which is what actually lets the compiler auto-vectorize this —
+/// a real SIMD `u32x8`/`u32x16` register would need `target_feature`/`unsafe`, which
+/// `#![deny(unsafe_code)]` rules out. Bit-identical to the scalar path for every input.
+#[cfg(feature = "simd-compress")]
 #[allow(clippy::cast_possible_truncation)] // last line (and const)
 pub(crate) fn compress_vector(d: u32, inout: &mut [Z]) {
+    let mut chunks = inout.chunks_exact_mut(SIMD_LANES);
+    for chunk in &mut chunks {
+        compress_chunk(d, chunk);
+    }
+    compress_chunk(d, chunks.into_remainder());
+}
+
+
+/// Core `Compress_d` recurrence, applied independently to every element of `inout`; shared by
+/// both the scalar and `simd-compress` chunked callers of [`compress_vector`] so the two paths
+/// cannot drift apart.
+#[allow(clippy::cast_possible_truncation)] // last line (and const)
+fn compress_chunk(d: u32, inout: &mut [Z]) {
     const M: u32 = (((1u64 << 36) + Q as u64 - 1) / Q as u64) as u32;
-    for x_ref in &mut *inout {
+    for x_ref in inout {
         let y = (x_ref.get_u32() << d) + (u32::from(Q) >> 1);
         let result = (u64::from(y) * u64::from(M)) >> 36;
         x_ref.set_u16(result as u16);
@@ -168,9 +199,31 @@ pub(crate) fn compress_vector(d: u32, inout: &mut [Z]) {
 /// Decompress<d> from page 18 (4.6).
 /// y → ⌈(q/2^d) · y⌋
 /// `d` comes from fixed security parameter, `inout` saves some allocation
+#[cfg(not(feature = "simd-compress"))]
 #[allow(clippy::cast_possible_truncation)] // last line
 pub(crate) fn decompress_vector(d: u32, inout: &mut [Z]) {
-    for y_ref in &mut *inout {
+    decompress_chunk(d, inout);
+}
+
+
+/// `Decompress_d`, `simd-compress` variant; see [`compress_vector`]'s `simd-compress` variant for
+/// why this chunks the recurrence instead of changing it.
+#[cfg(feature = "simd-compress")]
+#[allow(clippy::cast_possible_truncation)] // last line
+pub(crate) fn decompress_vector(d: u32, inout: &mut [Z]) {
+    let mut chunks = inout.chunks_exact_mut(SIMD_LANES);
+    for chunk in &mut chunks {
+        decompress_chunk(d, chunk);
+    }
+    decompress_chunk(d, chunks.into_remainder());
+}
+
+
+/// Core `Decompress_d` recurrence; see [`compress_chunk`] for why this is split out of
+/// [`decompress_vector`].
+#[allow(clippy::cast_possible_truncation)] // last line
+fn decompress_chunk(d: u32, inout: &mut [Z]) {
+    for y_ref in inout {
         let qy = u32::from(Q) * y_ref.get_u32() + (1 << d) - 1;
         y_ref.set_u16((qy >> d) as u16);
     }
@@ -0,0 +1,290 @@
+//! RFC 9180-style Hybrid Public Key Encryption (HPKE), base mode only (no PSK, no sender auth).
+//!
+//! This turns the bare KEM exposed via the `Encaps`/`Decaps` traits into an interface for
+//! encrypting arbitrary-length messages to an `EncapsKey`, rather than just agreeing a 32-byte
+//! `SharedSecretKey`. The KEM step is whatever `Encaps`/`Decaps` implementation the caller
+//! passes in (e.g. `ml_kem_768::{EncapsKey, DecapsKey}`); on top of the resulting shared secret
+//! this module runs the RFC 9180 §5.1 `LabeledExtract`/`LabeledExpand` key schedule to derive an
+//! AEAD key, base nonce and exporter secret, then layers AES-256-GCM or `ChaCha20Poly1305` for
+//! the actual message encryption. A `Context` can `seal`/`open` several messages in sequence
+//! (the nonce is `base_nonce` XOR a per-context counter); [`seal`] and [`open`] are one-shot
+//! convenience wrappers around a single-message `Context`.
+//!
+//! ML-KEM does not have an IANA-assigned RFC 9180 KEM id, so the `KEM_ID` bytes mixed into the
+//! key schedule below are an implementation-defined placeholder, not a registered value; this
+//! module is an HPKE-shaped construction over ML-KEM, not a drop-in RFC 9180 ciphersuite.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use hkdf::Hkdf;
+use rand_core::CryptoRngCore;
+use sha2::Sha256;
+
+use crate::aead_dispatch::AeadError;
+pub use crate::aead_dispatch::AeadAlg;
+use crate::traits::{Decaps, Encaps, SerDes};
+use crate::SharedSecretKey;
+
+const VERSION_LABEL: &[u8] = b"HPKE-v1";
+// Implementation-defined placeholder: ML-KEM has no IANA HPKE KEM id.
+const KEM_ID: [u8; 2] = [0xFF, 0x00];
+// HKDF-SHA256, matching the IANA HPKE KDF id `0x0001`.
+const KDF_ID: [u8; 2] = [0x00, 0x01];
+const MODE_BASE: u8 = 0x00;
+const NK: usize = 32; // key size for both supported AEADs
+const NN: usize = 12; // nonce size for both supported AEADs
+const NH: usize = 32; // HKDF-SHA256 digest size
+
+
+impl AeadAlg {
+    /// IANA HPKE AEAD id: `0x0002` for AES-256-GCM, `0x0003` for `ChaCha20Poly1305`.
+    fn id(self) -> [u8; 2] {
+        match self {
+            Self::Aes256Gcm => [0x00, 0x02],
+            Self::ChaCha20Poly1305 => [0x00, 0x03],
+        }
+    }
+}
+
+/// Maps the AEAD-dispatch error shared with [`crate::seal`] onto this module's own
+/// `"HPKE: ..."`-prefixed messages.
+fn map_err(e: AeadError) -> &'static str {
+    match e {
+        AeadError::BadKeyLength(AeadAlg::Aes256Gcm) => "HPKE: bad AES-256-GCM key length",
+        AeadError::BadKeyLength(AeadAlg::ChaCha20Poly1305) => "HPKE: bad ChaCha20Poly1305 key length",
+        AeadError::SealFailed => "HPKE: AEAD seal failed",
+        AeadError::OpenFailed => "HPKE: AEAD open failed",
+    }
+}
+
+
+/// `suite_id = "HPKE" || kem_id || kdf_id || aead_id`, per RFC 9180 §5.1.
+fn suite_id(aead_alg: AeadAlg) -> [u8; 10] {
+    let mut id = [0u8; 10];
+    id[0..4].copy_from_slice(b"HPKE");
+    id[4..6].copy_from_slice(&KEM_ID);
+    id[6..8].copy_from_slice(&KDF_ID);
+    id[8..10].copy_from_slice(&aead_alg.id());
+    id
+}
+
+
+/// `LabeledExtract(salt, label, ikm) = HKDF-Extract(salt, "HPKE-v1" || suite_id || label || ikm)`
+fn labeled_extract(salt: &[u8], suite_id: &[u8; 10], label: &[u8], ikm: &[u8]) -> [u8; NH] {
+    let mut labeled_ikm =
+        Vec::with_capacity(VERSION_LABEL.len() + suite_id.len() + label.len() + ikm.len());
+    labeled_ikm.extend_from_slice(VERSION_LABEL);
+    labeled_ikm.extend_from_slice(suite_id);
+    labeled_ikm.extend_from_slice(label);
+    labeled_ikm.extend_from_slice(ikm);
+    let (prk, _) = Hkdf::<Sha256>::extract(Some(salt), &labeled_ikm);
+    let mut out = [0u8; NH];
+    out.copy_from_slice(&prk);
+    out
+}
+
+
+/// `LabeledExpand(prk, label, info, L) = HKDF-Expand(prk, I2OSP(L,2) || "HPKE-v1" || suite_id || label || info, L)`
+fn labeled_expand(
+    prk: &[u8; NH], suite_id: &[u8; 10], label: &[u8], info: &[u8], out: &mut [u8],
+) -> Result<(), &'static str> {
+    let len_be = u16::try_from(out.len())
+        .map_err(|_| "HPKE: expand output length exceeds u16")?
+        .to_be_bytes();
+    let mut labeled_info =
+        Vec::with_capacity(2 + VERSION_LABEL.len() + suite_id.len() + label.len() + info.len());
+    labeled_info.extend_from_slice(&len_be);
+    labeled_info.extend_from_slice(VERSION_LABEL);
+    labeled_info.extend_from_slice(suite_id);
+    labeled_info.extend_from_slice(label);
+    labeled_info.extend_from_slice(info);
+    let hk = Hkdf::<Sha256>::from_prk(prk).map_err(|_| "HPKE: bad PRK length")?;
+    hk.expand(&labeled_info, out).map_err(|_| "HPKE: expand output too long")
+}
+
+
+/// Base-mode RFC 9180 §5.1 key schedule, producing the `Context` used to `seal`/`open` messages.
+fn key_schedule(shared_secret: &[u8; 32], info: &[u8], aead_alg: AeadAlg) -> Result<Context, &'static str> {
+    let sid = suite_id(aead_alg);
+
+    // key_schedule_context = mode || psk_id_hash || info_hash    (psk_id is empty in base mode)
+    let psk_id_hash = labeled_extract(&[], &sid, b"psk_id_hash", &[]);
+    let info_hash = labeled_extract(&[], &sid, b"info_hash", info);
+    let mut context = Vec::with_capacity(1 + psk_id_hash.len() + info_hash.len());
+    context.push(MODE_BASE);
+    context.extend_from_slice(&psk_id_hash);
+    context.extend_from_slice(&info_hash);
+
+    // secret = LabeledExtract(shared_secret, "secret", psk)    (psk is empty in base mode)
+    let secret = labeled_extract(shared_secret, &sid, b"secret", &[]);
+
+    let mut key = [0u8; NK];
+    labeled_expand(&secret, &sid, b"key", &context, &mut key)?;
+    let mut base_nonce = [0u8; NN];
+    labeled_expand(&secret, &sid, b"base_nonce", &context, &mut base_nonce)?;
+    let mut exporter_secret = [0u8; NH];
+    labeled_expand(&secret, &sid, b"exp", &context, &mut exporter_secret)?;
+
+    Ok(Context { aead_alg, key, base_nonce, exporter_secret, seq: 0 })
+}
+
+
+/// An HPKE context bound to a single key schedule (RFC 9180 §5.2), usable to `seal` or `open`
+/// several messages in order, and to derive further independent secrets via [`Context::export`].
+/// Each `seal`/`open` call encrypts/decrypts under `base_nonce XOR seq` and then advances `seq`;
+/// it is an error for `seq` to overflow rather than reuse a nonce.
+pub struct Context {
+    aead_alg: AeadAlg,
+    key: [u8; NK],
+    base_nonce: [u8; NN],
+    exporter_secret: [u8; NH],
+    seq: u64,
+}
+
+impl Context {
+    fn current_nonce(&self) -> [u8; NN] {
+        let seq_be = self.seq.to_be_bytes(); // 8 bytes, right-aligned into the 12-byte nonce
+        let mut nonce = self.base_nonce;
+        for (n, s) in nonce.iter_mut().rev().zip(seq_be.iter().rev()) {
+            *n ^= s;
+        }
+        nonce
+    }
+
+    /// Encrypts `pt`, authenticating `aad`, under this context's current sequence number, and
+    /// advances the sequence number.
+    /// # Errors
+    /// Returns an error if the sequence number has overflowed or the AEAD itself fails.
+    pub fn seal(&mut self, aad: &[u8], pt: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let nonce = self.current_nonce();
+        let ct = self.aead_alg.seal(&self.key, &nonce, aad, pt).map_err(map_err)?;
+        self.seq = self.seq.checked_add(1).ok_or("HPKE: sequence number overflow")?;
+        Ok(ct)
+    }
+
+    /// Decrypts `ct`, checking `aad`, under this context's current sequence number, and advances
+    /// the sequence number on success.
+    /// # Errors
+    /// Returns an error if the sequence number has overflowed or authentication fails.
+    pub fn open(&mut self, aad: &[u8], ct: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let nonce = self.current_nonce();
+        let pt = self.aead_alg.open(&self.key, &nonce, aad, ct).map_err(map_err)?;
+        self.seq = self.seq.checked_add(1).ok_or("HPKE: sequence number overflow")?;
+        Ok(pt)
+    }
+
+    /// Derives `length` independent bytes from this context's exporter secret (RFC 9180 §5.3
+    /// `Context.Export`): `HKDF-Expand(exporter_secret, "HPKE-v1" || suite_id || "sec" ||
+    /// exporter_context, length)`. This lets one encaps/decaps operation key several independent
+    /// channels (e.g. separate send/receive keys, or successive rekeying epochs) without
+    /// consuming the `seal`/`open` AEAD key or requiring another KEM round trip.
+    /// # Errors
+    /// Returns an error if `length` cannot be represented as a `u16` or HKDF-Expand rejects it.
+    pub fn export(&self, exporter_context: &[u8], length: usize) -> Result<Vec<u8>, &'static str> {
+        let sid = suite_id(self.aead_alg);
+        let mut out = alloc::vec![0u8; length];
+        labeled_expand(&self.exporter_secret, &sid, b"sec", exporter_context, &mut out)?;
+        Ok(out)
+    }
+}
+
+
+/// Runs the KEM step against `ek` (RFC 9180 `SetupBaseS`) and derives a sender [`Context`] for
+/// one or more subsequent `seal()` calls.
+/// # Errors
+/// Returns an error if the KEM or key schedule derivation fails.
+pub fn setup_sender<E>(
+    ek: &E, info: &[u8], aead_alg: AeadAlg, rng: &mut impl CryptoRngCore,
+) -> Result<(E::CipherText, Context), &'static str>
+where E: Encaps<SharedSecretKey = SharedSecretKey> {
+    let (ssk, ct) = ek.try_encaps_with_rng(rng)?;
+    let context = key_schedule(&ssk.into_bytes(), info, aead_alg)?;
+    Ok((ct, context))
+}
+
+
+/// Runs the KEM step against `dk` and the sender's KEM ciphertext `enc` (RFC 9180 `SetupBaseR`)
+/// and derives a receiver [`Context`] for one or more subsequent `open()` calls.
+/// # Errors
+/// Returns an error if the KEM or key schedule derivation fails.
+pub fn setup_receiver<D>(dk: &D, enc: &D::CipherText, info: &[u8], aead_alg: AeadAlg) -> Result<Context, &'static str>
+where D: Decaps<SharedSecretKey = SharedSecretKey> {
+    let ssk = dk.try_decaps(enc)?;
+    key_schedule(&ssk.into_bytes(), info, aead_alg)
+}
+
+
+/// One-shot HPKE seal: runs the KEM against `ek`, derives a context, and encrypts `pt` as that
+/// context's only message. Returns `(enc, ciphertext)`, where `enc` is the KEM ciphertext the
+/// receiver needs alongside `ciphertext`.
+/// # Errors
+/// Returns an error if the KEM, key schedule, or AEAD step fails.
+pub fn seal<E>(
+    ek: &E, info: &[u8], aad: &[u8], pt: &[u8], aead_alg: AeadAlg, rng: &mut impl CryptoRngCore,
+) -> Result<(E::CipherText, Vec<u8>), &'static str>
+where E: Encaps<SharedSecretKey = SharedSecretKey> {
+    let (enc, mut context) = setup_sender(ek, info, aead_alg, rng)?;
+    let ciphertext = context.seal(aad, pt)?;
+    Ok((enc, ciphertext))
+}
+
+
+/// One-shot HPKE open: reverses [`seal`] given the KEM ciphertext `enc` and the AEAD
+/// ciphertext, using `dk`.
+/// # Errors
+/// Returns an error if the KEM, key schedule, or AEAD step fails.
+pub fn open<D>(
+    dk: &D, enc: &D::CipherText, info: &[u8], aad: &[u8], ciphertext: &[u8], aead_alg: AeadAlg,
+) -> Result<Vec<u8>, &'static str>
+where D: Decaps<SharedSecretKey = SharedSecretKey> {
+    let mut context = setup_receiver(dk, enc, info, aead_alg)?;
+    context.open(aad, ciphertext)
+}
+
+
+#[cfg(all(test, feature = "ml-kem-512"))]
+mod tests {
+    use rand_core::SeedableRng;
+
+    use super::{open, seal, setup_sender, AeadAlg};
+    use crate::ml_kem_512::KG;
+    use crate::traits::KeyGen;
+
+    #[test]
+    fn test_seal_open_roundtrip() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(55);
+        let (ek, dk) = KG::try_keygen_with_rng(&mut rng).unwrap();
+        let pt = b"the eagle has landed";
+        let aad = b"header";
+
+        for aead_alg in [AeadAlg::Aes256Gcm, AeadAlg::ChaCha20Poly1305] {
+            let (enc, ct) = seal(&ek, b"info", aad, pt, aead_alg, &mut rng).unwrap();
+            let opened = open(&dk, &enc, b"info", aad, &ct, aead_alg).unwrap();
+            assert_eq!(opened, pt);
+        }
+    }
+
+    #[test]
+    fn test_open_rejects_wrong_aad() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(56);
+        let (ek, dk) = KG::try_keygen_with_rng(&mut rng).unwrap();
+        let (enc, ct) = seal(&ek, b"info", b"aad1", b"message", AeadAlg::Aes256Gcm, &mut rng).unwrap();
+        assert!(open(&dk, &enc, b"info", b"aad2", &ct, AeadAlg::Aes256Gcm).is_err());
+    }
+
+    #[test]
+    fn test_export_derives_independent_secrets() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(57);
+        let (ek, _dk) = KG::try_keygen_with_rng(&mut rng).unwrap();
+        let (_enc, context) = setup_sender(&ek, b"info", AeadAlg::Aes256Gcm, &mut rng).unwrap();
+
+        let a = context.export(b"first channel", 32).unwrap();
+        let b = context.export(b"second channel", 32).unwrap();
+        assert_ne!(a, b, "distinct exporter_context values must yield distinct secrets");
+
+        let a_again = context.export(b"first channel", 32).unwrap();
+        assert_eq!(a, a_again, "export is deterministic for a fixed context and length");
+    }
+}
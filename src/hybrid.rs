@@ -0,0 +1,148 @@
+//! A single-shot "KEM-DEM" hybrid public-key encryption helper built directly on the
+//! `Encaps`/`Decaps` traits and AES-256-GCM, for callers who want to protect a file or message
+//! under an `EncapsKey` without assembling the KEM and a separate AEAD crate by hand.
+//!
+//! [`encrypt`] encapsulates to `ek`, runs the resulting `SharedSecretKey` through HKDF-SHA256
+//! (domain-separated with a fixed info label) to derive a 32-byte AES-256-GCM key, draws a
+//! random 96-bit nonce, and returns the self-contained blob
+//! `ml_kem_ciphertext || nonce || gcm_ciphertext_and_tag`. [`decrypt`] reverses this: it splits
+//! the blob (the ML-KEM ciphertext length is fixed by `D::CipherText`'s serialized size),
+//! decapsulates to re-derive the same AES-256-GCM key, and verifies the tag in constant time
+//! (via the AEAD crate's own constant-time tag comparison). Because ML-KEM is IND-CCA, this
+//! composition is a clean IND-CCA public-key encryption primitive.
+//!
+//! This differs from the [`crate::hpke`] module in being AES-256-GCM-only, using a plain
+//! (non-RFC-9180) HKDF derivation, and packing everything into one self-contained blob rather
+//! than a separate KEM-ciphertext/AEAD-ciphertext pair plus a multi-message `Context`.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use aead::{Aead as _, KeyInit as _, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use hkdf::Hkdf;
+use rand_core::CryptoRngCore;
+use sha2::Sha256;
+
+use crate::helpers::ensure;
+use crate::traits::{Decaps, Encaps, SerDes};
+use crate::{SharedSecretKey, SSK_LEN};
+
+const HKDF_INFO: &[u8] = b"fips203-hybrid-kem-dem-aes256gcm-v1";
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+
+/// Derives the AES-256-GCM key from the raw `SharedSecretKey` bytes via HKDF-SHA256, domain
+/// separated from any other use of the shared secret by [`HKDF_INFO`].
+fn derive_key(shared_secret: &[u8; SSK_LEN]) -> [u8; KEY_LEN] {
+    let hk = Hkdf::<Sha256>::new(None, shared_secret);
+    let mut key = [0u8; KEY_LEN];
+    // 32 bytes is far below HKDF-SHA256's 255*32-byte output limit, so this cannot fail.
+    hk.expand(HKDF_INFO, &mut key).expect("HKDF-Expand to 32 bytes cannot fail");
+    key
+}
+
+
+/// Encapsulates to `ek` and encrypts `plaintext` (authenticating `aad`) into a single
+/// self-contained blob: `ml_kem_ciphertext || nonce || gcm_ciphertext_and_tag`.
+/// # Errors
+/// Returns an error if the KEM, the random number generator, or the AEAD step fails.
+pub fn encrypt<E>(
+    ek: &E, plaintext: &[u8], aad: &[u8], rng: &mut impl CryptoRngCore,
+) -> Result<Vec<u8>, &'static str>
+where
+    E: Encaps<SharedSecretKey = SharedSecretKey>,
+    E::CipherText: SerDes,
+    <E::CipherText as SerDes>::ByteArray: AsRef<[u8]>,
+{
+    let (ssk, ct) = ek.try_encaps_with_rng(rng)?;
+    let key = derive_key(&ssk.into_bytes());
+
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rng.try_fill_bytes(&mut nonce_bytes).map_err(|_| "hybrid: random number generator failed")?;
+
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| "hybrid: bad AES-256-GCM key length")?;
+    let gcm_ct = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), Payload { msg: plaintext, aad })
+        .map_err(|_| "hybrid: AEAD seal failed")?;
+
+    let ct_bytes = ct.into_bytes();
+    let ct_bytes = ct_bytes.as_ref();
+    let mut blob = Vec::with_capacity(ct_bytes.len() + NONCE_LEN + gcm_ct.len());
+    blob.extend_from_slice(ct_bytes);
+    blob.extend_from_slice(&nonce_bytes);
+    blob.extend_from_slice(&gcm_ct);
+    Ok(blob)
+}
+
+
+/// Reverses [`encrypt`]: splits `blob` (the ML-KEM ciphertext length is fixed by
+/// `D::CipherText`'s serialized size), decapsulates with `dk` to re-derive the AES-256-GCM key,
+/// and verifies the tag over `aad`.
+/// # Errors
+/// Returns an error if `blob` is too short, the KEM fails, or authentication fails.
+pub fn decrypt<D>(dk: &D, blob: &[u8], aad: &[u8]) -> Result<Vec<u8>, &'static str>
+where
+    D: Decaps<SharedSecretKey = SharedSecretKey>,
+    D::CipherText: SerDes,
+    <D::CipherText as SerDes>::ByteArray: Default + AsMut<[u8]> + AsRef<[u8]>,
+{
+    let ct_len = core::mem::size_of::<<D::CipherText as SerDes>::ByteArray>();
+    ensure!(blob.len() >= ct_len + NONCE_LEN, "hybrid: blob too short");
+
+    let mut ct_bytes = <D::CipherText as SerDes>::ByteArray::default();
+    ct_bytes.as_mut().copy_from_slice(&blob[..ct_len]);
+    let ct = D::CipherText::try_from_bytes(ct_bytes)?;
+
+    let nonce_bytes = &blob[ct_len..ct_len + NONCE_LEN];
+    let gcm_ct = &blob[ct_len + NONCE_LEN..];
+
+    let ssk = dk.try_decaps(&ct)?;
+    let key = derive_key(&ssk.into_bytes());
+
+    let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| "hybrid: bad AES-256-GCM key length")?;
+    cipher
+        .decrypt(Nonce::from_slice(nonce_bytes), Payload { msg: gcm_ct, aad })
+        .map_err(|_| "hybrid: AEAD open failed")
+}
+
+
+#[cfg(all(test, feature = "ml-kem-512"))]
+mod tests {
+    use rand_core::SeedableRng;
+
+    use super::{decrypt, encrypt};
+    use crate::ml_kem_512::KG;
+    use crate::traits::KeyGen;
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(77);
+        let (ek, dk) = KG::try_keygen_with_rng(&mut rng).unwrap();
+        let plaintext = b"a message protected end-to-end";
+        let aad = b"context";
+
+        let blob = encrypt(&ek, plaintext, aad, &mut rng).unwrap();
+        let recovered = decrypt(&dk, &blob, aad).unwrap();
+        assert_eq!(recovered, plaintext);
+    }
+
+    #[test]
+    fn test_decrypt_rejects_tampered_blob() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(78);
+        let (ek, dk) = KG::try_keygen_with_rng(&mut rng).unwrap();
+        let mut blob = encrypt(&ek, b"message", b"aad", &mut rng).unwrap();
+        let last = blob.len() - 1;
+        blob[last] ^= 0x01;
+        assert!(decrypt(&dk, &blob, b"aad").is_err());
+    }
+
+    #[test]
+    fn test_decrypt_rejects_short_blob() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(79);
+        let (_ek, dk) = KG::try_keygen_with_rng(&mut rng).unwrap();
+        assert!(decrypt(&dk, &[0u8; 4], b"aad").is_err());
+    }
+}
@@ -1,4 +1,7 @@
+use crate::backend::Sha3Backend;
 use crate::byte_fns::{byte_decode, byte_encode};
+#[cfg(feature = "batched-xof")]
+use crate::helpers::xof_batch;
 use crate::helpers::{
     add_vecs, compress_vector, decompress_vector, dot_t_prod, g, mul_mat_t_vec, mul_mat_vec, prf,
     xof,
@@ -7,6 +10,8 @@ use crate::ntt::{ntt, ntt_inv};
 use crate::sampling::{sample_ntt, sample_poly_cbd};
 use crate::types::Z;
 use rand_core::CryptoRngCore;
+#[cfg(feature = "zeroize-internals")]
+use zeroize::Zeroize;
 
 
 /// Algorithm 12 `K-PKE.KeyGen()` on page 26.
@@ -15,31 +20,53 @@ use rand_core::CryptoRngCore;
 /// Output: encryption key `ekPKE ∈ B^{384·k+32}` <br>
 /// Output: decryption key `dkPKE ∈ B^{384·k}`
 #[allow(clippy::similar_names)]
-pub(crate) fn k_pke_key_gen<const K: usize, const ETA1_64: usize>(
+pub(crate) fn k_pke_key_gen<const K: usize, const ETA1_64: usize, B: Sha3Backend>(
     rng: &mut impl CryptoRngCore, ek_pke: &mut [u8], dk_pke: &mut [u8],
 ) -> Result<(), &'static str> {
-    debug_assert_eq!(ek_pke.len(), 384 * K + 32, "Alg12: ek_pke not 384 * K + 32");
-    debug_assert_eq!(dk_pke.len(), 384 * K, "Alg12: dk_pke not 384 * K");
-
     // 1: d ←− B^{32}    ▷ d is 32 random bytes (see Section 3.3)
     let mut d = [0u8; 32];
     rng.try_fill_bytes(&mut d).map_err(|_| "Alg12: random number generator failed")?;
 
+    let result = k_pke_key_gen_internal::<K, ETA1_64, B>(&d, ek_pke, dk_pke);
+    // `d` is no longer needed once the internal algorithm has consumed it; wipe it from the
+    // stack (requires the `zeroize-internals` feature, as it comes at a small perf cost).
+    #[cfg(feature = "zeroize-internals")]
+    d.zeroize();
+    result
+}
+
+
+/// Algorithm 12 `K-PKE.KeyGen()` on page 26, deterministic variant that takes the seed `d`
+/// directly rather than drawing it from an RNG; split out so `ML-KEM.KeyGen_internal(d, z)`
+/// can be offered to callers that need bit-exact reproduction (e.g. ACVP/KAT vectors).
+///
+/// Input: seed `d` ∈ `B^{32}` <br>
+/// Output: encryption key `ekPKE ∈ B^{384·k+32}` <br>
+/// Output: decryption key `dkPKE ∈ B^{384·k}`
+#[allow(clippy::similar_names)]
+pub(crate) fn k_pke_key_gen_internal<const K: usize, const ETA1_64: usize, B: Sha3Backend>(
+    d: &[u8; 32], ek_pke: &mut [u8], dk_pke: &mut [u8],
+) -> Result<(), &'static str> {
+    debug_assert_eq!(ek_pke.len(), 384 * K + 32, "Alg12: ek_pke not 384 * K + 32");
+    debug_assert_eq!(dk_pke.len(), 384 * K, "Alg12: dk_pke not 384 * K");
+
     // 2: (ρ, σ) ← G(d)    ▷ expand to two pseudorandom 32-byte seeds
-    let (rho, sigma) = g(&[&d]);
+    #[cfg_attr(not(feature = "zeroize-internals"), allow(unused_mut))]
+    let (rho, mut sigma) = g::<B>(&[d]);
 
     // 3: N ← 0
     let mut n = 0;
 
     // Steps 4-8 in gen_a_hat() below
-    let a_hat = gen_a_hat(&rho);
+    let a_hat = gen_a_hat::<K, B>(&rho);
 
     // 9: for (i ← 0; i < k; i ++)    ▷ generate s ∈ (Z_q^{256})^k
     // 10: s[i] ← SamplePolyCBDη1(PRFη1(σ, N))    ▷ s[i] ∈ Z^{256}_q sampled from CBD
     // 11: N ← N +1
     // 12: end for
-    let s: [[Z; 256]; K] = core::array::from_fn(|_| {
-        let x = sample_poly_cbd(&prf::<ETA1_64>(&sigma, n));
+    #[cfg_attr(not(feature = "zeroize-internals"), allow(unused_mut))]
+    let mut s: [[Z; 256]; K] = core::array::from_fn(|_| {
+        let x = sample_poly_cbd(&prf::<ETA1_64, B>(&sigma, n));
         n += 1;
         x
     });
@@ -49,13 +76,20 @@ pub(crate) fn k_pke_key_gen<const K: usize, const ETA1_64: usize>(
     // 15: N ← N +1
     // 16: end for
     let e: [[Z; 256]; K] = core::array::from_fn(|_| {
-        let x = sample_poly_cbd(&prf::<ETA1_64>(&sigma, n));
+        let x = sample_poly_cbd(&prf::<ETA1_64, B>(&sigma, n));
         n += 1;
         x
     });
+    // σ is not needed past this point.
+    #[cfg(feature = "zeroize-internals")]
+    sigma.zeroize();
 
     // 17: s_hat ← NTT(s)    ▷ NTT is run k times (once for each coordinate of s)
-    let s_hat: [[Z; 256]; K] = core::array::from_fn(|i| ntt(&s[i]));
+    #[cfg_attr(not(feature = "zeroize-internals"), allow(unused_mut))]
+    let mut s_hat: [[Z; 256]; K] = core::array::from_fn(|i| ntt(&s[i]));
+    // s is not needed past this point; s_hat (its NTT) is what gets serialized below.
+    #[cfg(feature = "zeroize-internals")]
+    s.zeroize();
 
     // 18: ê ← NTT(e)    ▷ NTT is run k times
     let e_hat: [[Z; 256]; K] = core::array::from_fn(|i| ntt(&e[i]));
@@ -74,6 +108,9 @@ pub(crate) fn k_pke_key_gen<const K: usize, const ETA1_64: usize>(
     for i in 0..K {
         byte_encode(12, &s_hat[i], &mut dk_pke[i * 384..(i + 1) * 384]);
     }
+    // s_hat has now been serialized into dk_pke; the stack copy is no longer needed.
+    #[cfg(feature = "zeroize-internals")]
+    s_hat.zeroize();
 
     // 22: return (ekPKE , dkPKE )
     Ok(())
@@ -81,7 +118,8 @@ pub(crate) fn k_pke_key_gen<const K: usize, const ETA1_64: usize>(
 
 
 /// Shared function for `k_pke_key_gen()` and `k_pke_encrypt()`; steps 4-8
-fn gen_a_hat<const K: usize>(rho: &[u8; 32]) -> [[[Z; 256]; K]; K] {
+#[cfg(not(feature = "batched-xof"))]
+fn gen_a_hat<const K: usize, B: Sha3Backend>(rho: &[u8; 32]) -> [[[Z; 256]; K]; K] {
     //
     // 4: for (i ← 0; i < k; i++)    ▷ generate matrix A ∈ (Z^{256}_q)^{k×k}
     let mut a_hat = [[[Z::default(); 256]; K]; K];
@@ -92,7 +130,7 @@ fn gen_a_hat<const K: usize>(rho: &[u8; 32]) -> [[[Z; 256]; K]; K] {
             //
             // 6: A_hat[i, j] ← SampleNTT(XOF(ρ, i, j))    ▷ each entry of Â uniform in NTT domain
             // See page 21 regarding transpose of i, j -? j, i in XOF() https://csrc.nist.gov/files/pubs/fips/203/ipd/docs/fips-203-initial-public-comments-2023.pdf
-            *entry = sample_ntt(xof(rho, j.to_le_bytes()[0], i.to_le_bytes()[0]));
+            *entry = sample_ntt(xof::<B>(rho, j.to_le_bytes()[0], i.to_le_bytes()[0]));
 
             // 7: end for
         }
@@ -104,6 +142,32 @@ fn gen_a_hat<const K: usize>(rho: &[u8; 32]) -> [[[Z; 256]; K]; K] {
 }
 
 
+/// Shared function for `k_pke_key_gen()` and `k_pke_encrypt()`; steps 4-8, `batched-xof` variant.
+/// Fills an entire row `A_hat[i, ..]` from one batched XOF call instead of `K` sequential ones;
+/// see [`xof_batch`] for why this is a lane-interleaved loop rather than real SIMD.
+#[cfg(feature = "batched-xof")]
+fn gen_a_hat<const K: usize, B: Sha3Backend>(rho: &[u8; 32]) -> [[[Z; 256]; K]; K] {
+    //
+    // 4: for (i ← 0; i < k; i++)    ▷ generate matrix A ∈ (Z^{256}_q)^{k×k}
+    let mut a_hat = [[[Z::default(); 256]; K]; K];
+    for (i, row) in a_hat.iter_mut().enumerate().take(K) {
+        //
+        // 5-6: A_hat[i, 0..k] ← SampleNTT(XOF(ρ, j, i)) for all j at once
+        // See page 21 regarding transpose of i, j -? j, i in XOF() https://csrc.nist.gov/files/pubs/fips/203/ipd/docs/fips-203-initial-public-comments-2023.pdf
+        let i_byte = i.to_le_bytes()[0];
+        let ij: [(u8, u8); K] = core::array::from_fn(|j| (j.to_le_bytes()[0], i_byte));
+        let readers = xof_batch::<K, B>(rho, ij);
+        for (entry, reader) in row.iter_mut().zip(readers) {
+            *entry = sample_ntt(reader);
+        }
+
+        // 8: end for
+    }
+
+    a_hat
+}
+
+
 /// Algorithm 13 `K-PKE.Encrypt(ekPKE , m, r)` on page 27.
 /// Uses the encryption key to encrypt a plaintext message using the randomness r.
 ///
@@ -112,7 +176,12 @@ fn gen_a_hat<const K: usize>(rho: &[u8; 32]) -> [[[Z; 256]; K]; K] {
 /// Input: encryption randomness `r` ∈ `B^{32}` <br>
 /// Output: ciphertext `c` ∈ `B^{32(du·k+dv)}` <br>
 #[allow(clippy::many_single_char_names, clippy::too_many_arguments)]
-pub(crate) fn k_pke_encrypt<const K: usize, const ETA1_64: usize, const ETA2_64: usize>(
+pub(crate) fn k_pke_encrypt<
+    const K: usize,
+    const ETA1_64: usize,
+    const ETA2_64: usize,
+    B: Sha3Backend,
+>(
     du: u32, dv: u32, ek: &[u8], m: &[u8], randomness: &[u8; 32], ct: &mut [u8],
 ) -> Result<(), &'static str> {
     debug_assert_eq!(ek.len(), 384 * K + 32, "Alg 13: ek len not 384 * K + 32");
@@ -131,14 +200,14 @@ pub(crate) fn k_pke_encrypt<const K: usize, const ETA1_64: usize, const ETA2_64:
     let rho = &ek[384 * K..(384 * K + 32)].try_into().unwrap();
 
     // Steps 4-8 in gen_a_hat() above
-    let a_hat = gen_a_hat(rho);
+    let a_hat = gen_a_hat::<K, B>(rho);
 
     // 9: for (i ← 0; i < k; i ++)
     // 10: r[i] ← SamplePolyCBDη 1 (PRFη 1 (r, N))    ▷ r[i] ∈ Z^{256}_q sampled from CBD
     // 11: N ← N +1
     // 12: end for
     let r: [[Z; 256]; K] = core::array::from_fn(|_| {
-        let x = sample_poly_cbd(&prf::<ETA1_64>(randomness, n));
+        let x = sample_poly_cbd(&prf::<ETA1_64, B>(randomness, n));
         n += 1;
         x
     });
@@ -148,13 +217,13 @@ pub(crate) fn k_pke_encrypt<const K: usize, const ETA1_64: usize, const ETA2_64:
     // 15: N ← N +1
     // 16: end for
     let e1: [[Z; 256]; K] = core::array::from_fn(|_| {
-        let x = sample_poly_cbd(&prf::<ETA2_64>(randomness, n));
+        let x = sample_poly_cbd(&prf::<ETA2_64, B>(randomness, n));
         n += 1;
         x
     });
 
     // 17: e2 ← SamplePolyCBDη(PRFη2(r, N))    ▷ sample e2 ∈ Z^{256}_q from CBD
-    let e2 = sample_poly_cbd(&prf::<ETA2_64>(randomness, n));
+    let e2 = sample_poly_cbd(&prf::<ETA2_64, B>(randomness, n));
 
     // 18: r̂ ← NTT(r)    ▷ NTT is run k times
     let r_hat: [[Z; 256]; K] = core::array::from_fn(|i| ntt(&r[i]));
@@ -254,6 +323,7 @@ pub(crate) fn k_pke_decrypt<const K: usize>(
 mod tests {
     use rand_core::SeedableRng;
 
+    use crate::backend::DefaultSha3Backend;
     use crate::k_pke::{k_pke_decrypt, k_pke_encrypt, k_pke_key_gen};
 
     const ETA1: u32 = 3;
@@ -277,14 +347,14 @@ mod tests {
         let m = [0u8; 32];
         let r = [0u8; 32];
 
-        let res = k_pke_key_gen::<K, ETA1_64>(&mut rng, &mut ek, &mut dk[0..384 * K]);
+        let res = k_pke_key_gen::<K, ETA1_64, DefaultSha3Backend>(&mut rng, &mut ek, &mut dk[0..384 * K]);
         assert!(res.is_ok());
 
-        let res = k_pke_encrypt::<K, ETA1_64, ETA2_64>(DU, DV, &ek, &m, &r, &mut ct);
+        let res = k_pke_encrypt::<K, ETA1_64, ETA2_64, DefaultSha3Backend>(DU, DV, &ek, &m, &r, &mut ct);
         assert!(res.is_ok());
 
         let ff_ek = [0xFFu8; EK_LEN]; // oversized values
-        let res = k_pke_encrypt::<K, ETA1_64, ETA2_64>(DU, DV, &ff_ek, &m, &r, &mut ct);
+        let res = k_pke_encrypt::<K, ETA1_64, ETA2_64, DefaultSha3Backend>(DU, DV, &ff_ek, &m, &r, &mut ct);
         assert!(res.is_err());
 
         let res = k_pke_decrypt::<K>(DU, DV, &dk[0..384 * K], &ct);
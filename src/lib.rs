@@ -1,4 +1,4 @@
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(clippy::pedantic, warnings, missing_docs, unsafe_code)]
 // Most of the 'allow' category...
 #![deny(absolute_paths_not_starting_with_crate, box_pointers, dead_code)]
@@ -68,17 +68,46 @@ use crate::traits::SerDes;
 use subtle::ConstantTimeEq;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
+/// Shared `AeadAlg` selector and seal/open dispatch used by both `seal` and `hpke` (see their
+/// own doc comments for the derivations layered on top of it).
+#[cfg(any(feature = "seal", feature = "hpke"))]
+mod aead_dispatch;
+
 mod byte_fns;
 mod helpers;
 mod k_pke;
 mod ml_kem;
 mod ntt;
+mod ntt_backend;
 mod sampling;
 mod types;
 
 /// All functionality is covered by traits, such that consumers can utilize trait objects if desired.
 pub mod traits;
 
+/// Pluggable Keccak/SHA3 permutation backend used by the symmetric primitives in `helpers`.
+pub mod backend;
+
+/// Byte-wise Shamir secret sharing for `DecapsKey` material at rest; requires the `shamir` feature.
+#[cfg(feature = "shamir")]
+pub mod shamir;
+
+/// RFC 9180-style Hybrid Public Key Encryption built on the KEM; requires the `hpke` feature.
+#[cfg(feature = "hpke")]
+pub mod hpke;
+
+/// Single-blob KEM-DEM hybrid encryption (AES-256-GCM) built on the KEM; requires the `hybrid` feature.
+#[cfg(feature = "hybrid")]
+pub mod hybrid;
+
+/// X-Wing hybrid X25519/ML-KEM-768 KEM; requires the `x-wing` feature (and `ml-kem-768`).
+#[cfg(feature = "x-wing")]
+pub mod x_wing;
+
+/// Integrated KEM-DEM `seal`/`open` authenticated encryption built on the KEM; requires the `seal` feature.
+#[cfg(feature = "seal")]
+pub mod seal;
+
 // Relevant to all parameter sets
 const Q: u16 = 3329;
 const ZETA: u16 = 17;
@@ -88,10 +117,19 @@ const ZETA: u16 = 17;
 pub const SSK_LEN: usize = 32;
 
 /// The (opaque) secret key that can be de/serialized by each party.
-#[derive(Clone, Debug, Zeroize, ZeroizeOnDrop)]
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
 pub struct SharedSecretKey([u8; SSK_LEN]);
 
 
+// A hand-written (redacted) Debug avoids ever printing the shared secret, e.g. via a stray
+// `{:?}` in logging; `derive(Debug)` would leak the raw bytes.
+impl core::fmt::Debug for SharedSecretKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("SharedSecretKey").field(&"_").finish()
+    }
+}
+
+
 impl SerDes for SharedSecretKey {
     type ByteArray = [u8; SSK_LEN];
 
@@ -111,16 +149,80 @@ impl PartialEq for SharedSecretKey {
 }
 
 
+// `PartialEq` above is defined in terms of `ConstantTimeEq`; the trait itself is also
+// implemented directly so that `SharedSecretKey` can be used with `subtle`-based code
+// (e.g. `conditional_select`) without requiring callers to go through `PartialEq`.
+impl ConstantTimeEq for SharedSecretKey {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice { self.0.ct_eq(&other.0) }
+}
+
+
+// As with `EncapsKey`/`DecapsKey`/`CipherText`, serialize as a plain byte array for compact
+// formats and as hex for human-readable ones; see the `serde_byte_array!` macro in the
+// parameter-set modules for the equivalent logic (this type is not parameterized by `K`).
+#[cfg(feature = "serde")]
+impl serde::Serialize for SharedSecretKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self.clone().into_bytes();
+        if serializer.is_human_readable() {
+            let mut hex_buf = [0u8; 2 * SSK_LEN];
+            hex::encode_to_slice(bytes, &mut hex_buf).map_err(serde::ser::Error::custom)?;
+            let hex_str = core::str::from_utf8(&hex_buf).map_err(serde::ser::Error::custom)?;
+            serializer.serialize_str(hex_str)
+        } else {
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SharedSecretKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SsKVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for SsKVisitor {
+            type Value = [u8; SSK_LEN];
+
+            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "a {SSK_LEN}-byte array, or its hex encoding")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                let mut bytes = [0u8; SSK_LEN];
+                hex::decode_to_slice(v, &mut bytes).map_err(E::custom)?;
+                Ok(bytes)
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                v.try_into().map_err(|_| E::invalid_length(v.len(), &self))
+            }
+        }
+
+        let bytes = if deserializer.is_human_readable() {
+            deserializer.deserialize_str(SsKVisitor)?
+        } else {
+            deserializer.deserialize_bytes(SsKVisitor)?
+        };
+        SharedSecretKey::try_from_bytes(bytes).map_err(serde::de::Error::custom)
+    }
+}
+
+
 // This common functionality is injected into each parameter set module
 macro_rules! functionality {
     () => {
         use crate::byte_fns::byte_decode;
         use crate::helpers::{ensure, h};
-        use crate::ml_kem::{ml_kem_decaps, ml_kem_encaps, ml_kem_key_gen};
+        use crate::ml_kem::{
+            ml_kem_decaps, ml_kem_encaps, ml_kem_encaps_internal, ml_kem_key_gen,
+            ml_kem_key_gen_internal,
+        };
         use crate::traits::{Decaps, Encaps, KeyGen, SerDes};
         use crate::types::Z;
         use crate::SharedSecretKey;
         use rand_core::{CryptoRng, CryptoRngCore, RngCore};
+        #[cfg(feature = "kem")]
+        use kem::{Decapsulate, Encapsulate};
 
 
         /// Correctly sized encapsulation key specific to the target security parameter set.
@@ -146,7 +248,16 @@ macro_rules! functionality {
                 rng: &mut impl CryptoRngCore,
             ) -> Result<(EncapsKey, DecapsKey), &'static str> {
                 let (mut ek, mut dk) = ([0u8; EK_LEN], [0u8; DK_LEN]);
-                ml_kem_key_gen::<K, { ETA1 as usize * 64 }>(rng, &mut ek, &mut dk)?;
+                ml_kem_key_gen::<K, { ETA1 as usize * 64 }, Backend>(rng, &mut ek, &mut dk)?;
+                Ok((EncapsKey { 0: ek }, DecapsKey { 0: dk }))
+            }
+
+            #[cfg(feature = "deterministic")]
+            fn keygen_internal(
+                d: &[u8; 32], z: &[u8; 32],
+            ) -> Result<(EncapsKey, DecapsKey), &'static str> {
+                let (mut ek, mut dk) = ([0u8; EK_LEN], [0u8; DK_LEN]);
+                ml_kem_key_gen_internal::<K, { ETA1 as usize * 64 }, Backend>(d, z, &mut ek, &mut dk)?;
                 Ok((EncapsKey { 0: ek }, DecapsKey { 0: dk }))
             }
 
@@ -162,7 +273,7 @@ macro_rules! functionality {
                     return false;
                 };
                 // 2. dk should contain hash of ek
-                if !(h(ek) == dk[(len_dk_pke + len_ek_pke)..(len_dk_pke + len_ek_pke + 32)]) {
+                if !(h::<Backend>(ek) == dk[(len_dk_pke + len_ek_pke)..(len_dk_pke + len_ek_pke + 32)]) {
                     return false;
                 };
                 // 3. ek and dk should deserialize ok
@@ -211,11 +322,62 @@ macro_rules! functionality {
                 &self, rng: &mut impl CryptoRngCore,
             ) -> Result<(Self::SharedSecretKey, Self::CipherText), &'static str> {
                 let mut ct = [0u8; CT_LEN];
-                let ssk = ml_kem_encaps::<K, { ETA1 as usize * 64 }, { ETA2 as usize * 64 }>(
+                let ssk = ml_kem_encaps::<K, { ETA1 as usize * 64 }, { ETA2 as usize * 64 }, Backend>(
                     rng, DU, DV, &self.0, &mut ct,
                 )?;
                 Ok((ssk, CipherText { 0: ct }))
             }
+
+            #[cfg(feature = "deterministic")]
+            fn encaps_deterministic(
+                &self, m: &[u8; 32],
+            ) -> Result<(SharedSecretKey, CipherText), &'static str> {
+                let mut ct = [0u8; CT_LEN];
+                let ssk = ml_kem_encaps_internal::<
+                    K,
+                    { ETA1 as usize * 64 },
+                    { ETA2 as usize * 64 },
+                    Backend,
+                >(m, DU, DV, &self.0, &mut ct)?;
+                Ok((ssk, CipherText { 0: ct }))
+            }
+        }
+
+
+        impl KG {
+            /// Encapsulates to every key in `eks`, sampling a single message `m` and reusing it
+            /// across all of them (per Algorithm 16, each recipient's shared secret and
+            /// ciphertext randomness are still separately derived as `G(m ‖ H(ek_i))`, so a
+            /// shared `m` costs nothing in per-recipient binding or independence -- only the one
+            /// expensive random draw is amortized). Writes one shared secret and one ciphertext
+            /// per recipient into the caller-provided `out_ssks`/`out_cts`, which must be the
+            /// same length as `eks`; no heap allocation is used.
+            /// # Errors
+            /// Returns an error if `out_ssks`/`out_cts` aren't the same length as `eks`, the RNG
+            /// fails, or encapsulation to any individual key fails.
+            pub fn encaps_multi_with_rng(
+                eks: &[&EncapsKey], out_ssks: &mut [SharedSecretKey], out_cts: &mut [CipherText],
+                rng: &mut impl CryptoRngCore,
+            ) -> Result<(), &'static str> {
+                ensure!(
+                    eks.len() == out_ssks.len() && eks.len() == out_cts.len(),
+                    "out_ssks/out_cts must be the same length as eks"
+                );
+                let mut m = [0u8; 32];
+                rng.try_fill_bytes(&mut m).map_err(|_| "Alg16: random number generator failed")?;
+                for ((ek, out_ssk), out_ct) in eks.iter().zip(out_ssks.iter_mut()).zip(out_cts.iter_mut()) {
+                    let mut ct = [0u8; CT_LEN];
+                    let ssk = ml_kem_encaps_internal::<
+                        K,
+                        { ETA1 as usize * 64 },
+                        { ETA2 as usize * 64 },
+                        Backend,
+                    >(&m, DU, DV, &ek.0, &mut ct)?;
+                    *out_ssk = ssk;
+                    *out_ct = CipherText { 0: ct };
+                }
+                Ok(())
+            }
         }
 
 
@@ -230,12 +392,61 @@ macro_rules! functionality {
                     { ETA2 as usize * 64 },
                     { 32 + 32 * (DU as usize * K + DV as usize) },
                     CT_LEN,
+                    Backend,
                 >(DU, DV, &self.0, &ct.0);
                 ssk
             }
         }
 
 
+        /// Implements the RustCrypto `kem` crate's simplified `Encapsulate` trait, for
+        /// interoperability with generic protocol code (HPKE, Noise-style handshakes, etc.)
+        /// written against that ecosystem-standard interface rather than this crate's own
+        /// [`Encaps`] trait.
+        #[cfg(feature = "kem")]
+        impl Encapsulate<CipherText, SharedSecretKey> for EncapsKey {
+            type Error = &'static str;
+
+            fn encapsulate(
+                &self, rng: &mut impl CryptoRngCore,
+            ) -> Result<(CipherText, SharedSecretKey), Self::Error> {
+                let (ssk, ct) = self.try_encaps_with_rng(rng)?;
+                Ok((ct, ssk))
+            }
+        }
+
+
+        /// Implements the RustCrypto `kem` crate's simplified `Decapsulate` trait, for
+        /// interoperability with generic protocol code (HPKE, Noise-style handshakes, etc.)
+        /// written against that ecosystem-standard interface rather than this crate's own
+        /// [`Decaps`] trait.
+        #[cfg(feature = "kem")]
+        impl Decapsulate<CipherText, SharedSecretKey> for DecapsKey {
+            type Error = &'static str;
+
+            fn decapsulate(&self, encapsulated_key: &CipherText) -> Result<SharedSecretKey, Self::Error> {
+                self.try_decaps(encapsulated_key)
+            }
+        }
+
+
+        impl DecapsKey {
+            /// Recovers the encapsulation key embedded in this decapsulation key, so a caller that
+            /// only kept `dk` around (e.g. after loading it from storage) doesn't need to have
+            /// also kept `ek`. See `SerDes::try_from_bytes()` below: `dk` already carries `ek` and
+            /// `H(ek)` alongside the PKE decryption key, per FIPS 203 page 31, and both are
+            /// re-checked there on deserialization, so this just slices the same embedded copy out.
+            #[must_use]
+            pub fn to_encaps_key(&self) -> EncapsKey {
+                let len_ek_pke = 384 * K + 32;
+                let len_dk_pke = 384 * K;
+                let mut ek = [0u8; EK_LEN];
+                ek.copy_from_slice(&self.0[len_dk_pke..len_dk_pke + len_ek_pke]);
+                EncapsKey { 0: ek }
+            }
+        }
+
+
         impl SerDes for EncapsKey {
             type ByteArray = [u8; EK_LEN];
 
@@ -270,7 +481,7 @@ macro_rules! functionality {
                 let _res =
                     EncapsKey::try_from_bytes(ek.try_into().map_err(|_| "Malformed encaps key")?)?;
                 ensure!(
-                    h(ek) == dk[(len_dk_pke + len_ek_pke)..(len_dk_pke + len_ek_pke + 32)],
+                    h::<Backend>(ek) == dk[(len_dk_pke + len_ek_pke)..(len_dk_pke + len_ek_pke + 32)],
                     "Encaps hash wrong"
                 );
                 Ok(DecapsKey { 0: dk })
@@ -292,6 +503,69 @@ macro_rules! functionality {
         }
 
 
+        // Serializes as a fixed byte array for compact (bincode/CBOR-style) formats, and as a
+        // hex string for human-readable formats (JSON, TOML, ...); deserialization goes through
+        // `SerDes::try_from_bytes()` either way so the existing validation still applies. No
+        // heap allocation is needed since the crate is `no_std`.
+        #[cfg(feature = "serde")]
+        macro_rules! serde_byte_array {
+            ($type:ty, $len:expr) => {
+                impl serde::Serialize for $type {
+                    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        let bytes = self.clone().into_bytes();
+                        if serializer.is_human_readable() {
+                            let mut hex_buf = [0u8; 2 * $len];
+                            hex::encode_to_slice(bytes, &mut hex_buf).map_err(serde::ser::Error::custom)?;
+                            let hex_str =
+                                core::str::from_utf8(&hex_buf).map_err(serde::ser::Error::custom)?;
+                            serializer.serialize_str(hex_str)
+                        } else {
+                            serializer.serialize_bytes(&bytes)
+                        }
+                    }
+                }
+
+                impl<'de> serde::Deserialize<'de> for $type {
+                    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                        struct ByteArrayVisitor;
+
+                        impl<'de> serde::de::Visitor<'de> for ByteArrayVisitor {
+                            type Value = [u8; $len];
+
+                            fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                                write!(f, "a {}-byte array, or its hex encoding", $len)
+                            }
+
+                            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                                let mut bytes = [0u8; $len];
+                                hex::decode_to_slice(v, &mut bytes).map_err(E::custom)?;
+                                Ok(bytes)
+                            }
+
+                            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                                v.try_into().map_err(|_| E::invalid_length(v.len(), &self))
+                            }
+                        }
+
+                        let bytes = if deserializer.is_human_readable() {
+                            deserializer.deserialize_str(ByteArrayVisitor)?
+                        } else {
+                            deserializer.deserialize_bytes(ByteArrayVisitor)?
+                        };
+                        <$type>::try_from_bytes(bytes).map_err(serde::de::Error::custom)
+                    }
+                }
+            };
+        }
+
+        #[cfg(feature = "serde")]
+        serde_byte_array!(EncapsKey, EK_LEN);
+        #[cfg(feature = "serde")]
+        serde_byte_array!(DecapsKey, DK_LEN);
+        #[cfg(feature = "serde")]
+        serde_byte_array!(CipherText, CT_LEN);
+
+
         #[cfg(test)]
         mod tests {
             use super::*;
@@ -310,10 +584,109 @@ macro_rules! functionality {
                         &dk.clone().into_bytes()
                     ));
                     assert_eq!(ssk1, ssk2);
+                    assert_eq!(dk.to_encaps_key().0, ek.clone().0);
                     assert_eq!(ek.clone().0, EncapsKey::try_from_bytes(ek.into_bytes()).unwrap().0);
                     assert_eq!(dk.clone().0, DecapsKey::try_from_bytes(dk.into_bytes()).unwrap().0);
                 }
             }
+
+            #[test]
+            fn test_encaps_multi_with_rng() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(789);
+                let (ek1, dk1) = KG::try_keygen_with_rng(&mut rng).unwrap();
+                let (ek2, dk2) = KG::try_keygen_with_rng(&mut rng).unwrap();
+                let (ek3, dk3) = KG::try_keygen_with_rng(&mut rng).unwrap();
+                let eks = [&ek1, &ek2, &ek3];
+
+                let mut ssks: [SharedSecretKey; 3] =
+                    core::array::from_fn(|_| SharedSecretKey::try_from_bytes([0u8; 32]).unwrap());
+                let mut cts: [CipherText; 3] =
+                    core::array::from_fn(|_| CipherText::try_from_bytes([0u8; CT_LEN]).unwrap());
+                KG::encaps_multi_with_rng(&eks, &mut ssks, &mut cts, &mut rng).unwrap();
+
+                assert_eq!(dk1.try_decaps(&cts[0]).unwrap(), ssks[0]);
+                assert_eq!(dk2.try_decaps(&cts[1]).unwrap(), ssks[1]);
+                assert_eq!(dk3.try_decaps(&cts[2]).unwrap(), ssks[2]);
+                // Each recipient's shared secret is bound to its own ek, so a shared m doesn't
+                // make the three shared secrets collide.
+                assert_ne!(ssks[0], ssks[1]);
+                assert_ne!(ssks[1], ssks[2]);
+            }
+
+            #[cfg(feature = "deterministic")]
+            #[test]
+            fn test_keygen_encaps_internal() {
+                let d = [1u8; 32];
+                let z = [2u8; 32];
+                let m = [3u8; 32];
+                let (ek, dk) = KG::keygen_internal(&d, &z).unwrap();
+                let (ssk1, ct) = ek.encaps_deterministic(&m).unwrap();
+                let ssk2 = dk.try_decaps(&ct).unwrap();
+                assert_eq!(ssk1, ssk2);
+                // Deterministic inputs must yield deterministic outputs
+                let (ek2, dk2) = KG::keygen_internal(&d, &z).unwrap();
+                assert_eq!(ek.into_bytes(), ek2.into_bytes());
+                assert_eq!(dk.into_bytes(), dk2.into_bytes());
+            }
+
+            #[cfg(feature = "kem")]
+            #[test]
+            fn test_kem_traits() {
+                use kem::{Decapsulate, Encapsulate};
+
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(321);
+                let (ek, dk) = KG::try_keygen_with_rng(&mut rng).unwrap();
+                let (ct, ssk1) = ek.encapsulate(&mut rng).unwrap();
+                let ssk2 = dk.decapsulate(&ct).unwrap();
+                assert_eq!(ssk1, ssk2);
+            }
+
+            #[cfg(feature = "serde")]
+            #[test]
+            fn test_serde_roundtrip() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(654);
+                let (ek, dk) = KG::try_keygen_with_rng(&mut rng).unwrap();
+                let (ssk, ct) = ek.try_encaps_with_rng(&mut rng).unwrap();
+
+                // Human-readable (hex) round trip
+                let ek_json = serde_json::to_string(&ek).unwrap();
+                let ek2: EncapsKey = serde_json::from_str(&ek_json).unwrap();
+                assert_eq!(ek.clone().into_bytes(), ek2.into_bytes());
+
+                // Binary (fixed byte array) round trip
+                let dk_bin = bincode::serialize(&dk).unwrap();
+                let dk2: DecapsKey = bincode::deserialize(&dk_bin).unwrap();
+                assert_eq!(dk.into_bytes(), dk2.into_bytes());
+
+                let ssk_json = serde_json::to_string(&ssk).unwrap();
+                let ssk2: SharedSecretKey = serde_json::from_str(&ssk_json).unwrap();
+                assert_eq!(ssk, ssk2);
+
+                let ct_bin = bincode::serialize(&ct).unwrap();
+                let ct2: CipherText = bincode::deserialize(&ct_bin).unwrap();
+                assert_eq!(ct.into_bytes(), ct2.into_bytes());
+            }
+
+            #[cfg(feature = "serde")]
+            #[test]
+            fn test_serde_rejects_corrupted_bytes() {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(655);
+                let (ek, _dk) = KG::try_keygen_with_rng(&mut rng).unwrap();
+                let mut ek_bytes = ek.into_bytes();
+                // An out-of-range (>= q) coefficient fails `ByteDecode`'s range check, so this
+                // must be rejected by deserialization rather than silently accepted.
+                ek_bytes[0] = 0xFF;
+                ek_bytes[1] = 0xFF;
+                ek_bytes[2] = 0xFF;
+
+                let mut json_buf = [0u8; 2 * EK_LEN + 2];
+                json_buf[0] = b'"';
+                hex::encode_to_slice(ek_bytes, &mut json_buf[1..1 + 2 * EK_LEN]).unwrap();
+                json_buf[1 + 2 * EK_LEN] = b'"';
+                let ek_json = core::str::from_utf8(&json_buf).unwrap();
+
+                assert!(serde_json::from_str::<EncapsKey>(ek_json).is_err());
+            }
         }
     };
 }
@@ -351,6 +724,9 @@ pub mod ml_kem_512 {
     /// Serialized Ciphertext Key Length (in bytes)
     pub const CT_LEN: usize = 768;
 
+    /// SHA3/Keccak permutation backend used by this parameter set; see [`crate::backend`].
+    pub type Backend = crate::backend::DefaultSha3Backend;
+
     functionality!();
 }
 
@@ -387,6 +763,9 @@ pub mod ml_kem_768 {
     /// Serialized Ciphertext Key Length (in bytes)
     pub const CT_LEN: usize = 1088;
 
+    /// SHA3/Keccak permutation backend used by this parameter set; see [`crate::backend`].
+    pub type Backend = crate::backend::DefaultSha3Backend;
+
     functionality!();
 }
 
@@ -422,5 +801,8 @@ pub mod ml_kem_1024 {
     /// Serialized Ciphertext Key Length (in bytes)
     pub const CT_LEN: usize = 1568;
 
+    /// SHA3/Keccak permutation backend used by this parameter set; see [`crate::backend`].
+    pub type Backend = crate::backend::DefaultSha3Backend;
+
     functionality!();
 }
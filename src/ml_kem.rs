@@ -1,10 +1,13 @@
+use crate::backend::Sha3Backend;
 use crate::byte_fns::{byte_decode, byte_encode};
 use crate::helpers::{g, h, j};
-use crate::k_pke::{k_pke_decrypt, k_pke_encrypt, k_pke_key_gen};
+use crate::k_pke::{k_pke_decrypt, k_pke_encrypt, k_pke_key_gen_internal};
 use crate::types::Z;
 use crate::SharedSecretKey;
 use rand_core::CryptoRngCore;
 use subtle::{ConditionallySelectable, ConstantTimeEq};
+#[cfg(feature = "zeroize-internals")]
+use zeroize::Zeroize;
 
 
 /// Algorithm 15 `ML-KEM.KeyGen()` on page 29.
@@ -12,28 +15,48 @@ use subtle::{ConditionallySelectable, ConstantTimeEq};
 ///
 /// Output: Encapsulation key `ek` ∈ `B^{384·k+32}` <br>
 /// Output: Decapsulation key `dk` ∈ `B^{768·k+96}`
-pub(crate) fn ml_kem_key_gen<const K: usize, const ETA1_64: usize>(
+pub(crate) fn ml_kem_key_gen<const K: usize, const ETA1_64: usize, B: Sha3Backend>(
     rng: &mut impl CryptoRngCore, ek: &mut [u8], dk: &mut [u8],
 ) -> Result<(), &'static str> {
-    debug_assert_eq!(ek.len(), 384 * K + 32, "Alg 15: ek len not 384 * K + 32");
-    debug_assert_eq!(dk.len(), 768 * K + 96, "Alg 15: dk len not 768 * K + 96");
-
-    // 1: z ←− B32    ▷ z is 32 random bytes (see Section 3.3)
+    // 1: d ←− B32, z ←− B32    ▷ d, z are each 32 random bytes (see Section 3.3);
+    //    z is drawn from the RNG first, then d
     let mut z = [0u8; 32];
     rng.try_fill_bytes(&mut z)
         .map_err(|_| "Alg 15: Random number generator failed")?;
+    let mut d = [0u8; 32];
+    rng.try_fill_bytes(&mut d)
+        .map_err(|_| "Alg 15: Random number generator failed")?;
+
+    ml_kem_key_gen_internal::<K, ETA1_64, B>(&d, &z, ek, dk)
+}
 
-    // 2: (ek_{PKE}, dk_{PKE}) ← K-PKE.KeyGen()    ▷ run key generation for K-PKE
+
+/// Algorithm 16 (internal) `ML-KEM.KeyGen_internal(d, z)` on page 29.
+/// Deterministic variant of [`ml_kem_key_gen`] that takes the seeds `d` and `z` directly
+/// instead of drawing them from an RNG, so that callers can reproduce the FIPS 203
+/// KAT/ACVP vectors bit-exactly.
+///
+/// Input: seed `d` ∈ `B^{32}` <br>
+/// Input: seed `z` ∈ `B^{32}` <br>
+/// Output: Encapsulation key `ek` ∈ `B^{384·k+32}` <br>
+/// Output: Decapsulation key `dk` ∈ `B^{768·k+96}`
+pub(crate) fn ml_kem_key_gen_internal<const K: usize, const ETA1_64: usize, B: Sha3Backend>(
+    d: &[u8; 32], z: &[u8; 32], ek: &mut [u8], dk: &mut [u8],
+) -> Result<(), &'static str> {
+    debug_assert_eq!(ek.len(), 384 * K + 32, "Alg 15: ek len not 384 * K + 32");
+    debug_assert_eq!(dk.len(), 768 * K + 96, "Alg 15: dk len not 768 * K + 96");
+
+    // 2: (ek_{PKE}, dk_{PKE}) ← K-PKE.KeyGen(d)    ▷ run key generation for K-PKE
     let p1 = 384 * K;
-    k_pke_key_gen::<K, ETA1_64>(rng, ek, &mut dk[..p1])?; // 3: ek ← ekPKE
+    k_pke_key_gen_internal::<K, ETA1_64, B>(d, ek, &mut dk[..p1])?; // 3: ek ← ekPKE
 
     // 4: dk ← (dkPKE ∥ek∥H(ek)∥z)  (first concat element is done above alongside ek)
-    let h_ek = h(ek);
+    let h_ek = h::<B>(ek);
     let p2 = p1 + ek.len();
     let p3 = p2 + h_ek.len();
     dk[p1..p2].copy_from_slice(ek);
     dk[p2..p3].copy_from_slice(&h_ek);
-    dk[p3..].copy_from_slice(&z);
+    dk[p3..].copy_from_slice(z);
 
     // 5: return (ek, dk)
     Ok(())
@@ -46,8 +69,39 @@ pub(crate) fn ml_kem_key_gen<const K: usize, const ETA1_64: usize>(
 /// Validated input: encapsulation key `ek` ∈ `B^{384·k+32}` <br>
 /// Output: shared key `K` ∈ `B^{32}` <br>
 /// Output: ciphertext `c` ∈ `B^{32(du·k+dv)}` <br>
-pub(crate) fn ml_kem_encaps<const K: usize, const ETA1_64: usize, const ETA2_64: usize>(
+pub(crate) fn ml_kem_encaps<
+    const K: usize,
+    const ETA1_64: usize,
+    const ETA2_64: usize,
+    B: Sha3Backend,
+>(
     rng: &mut impl CryptoRngCore, du: u32, dv: u32, ek: &[u8], ct: &mut [u8],
+) -> Result<SharedSecretKey, &'static str> {
+    // 1: m ←− B32          ▷ m is 32 random bytes (see Section 3.3)
+    let mut m = [0u8; 32];
+    rng.try_fill_bytes(&mut m)
+        .map_err(|_| "Alg16: random number generator failed")?;
+
+    ml_kem_encaps_internal::<K, ETA1_64, ETA2_64, B>(&m, du, dv, ek, ct)
+}
+
+
+/// Algorithm 17 (internal) `ML-KEM.Encaps_internal(ek, m)` on page 30.
+/// Deterministic variant of [`ml_kem_encaps`] that takes the message `m` directly instead of
+/// drawing it from an RNG, so that callers can reproduce the FIPS 203 KAT/ACVP vectors
+/// bit-exactly.
+///
+/// Validated input: encapsulation key `ek` ∈ `B^{384·k+32}` <br>
+/// Input: message `m` ∈ `B^{32}` <br>
+/// Output: shared key `K` ∈ `B^{32}` <br>
+/// Output: ciphertext `c` ∈ `B^{32(du·k+dv)}` <br>
+pub(crate) fn ml_kem_encaps_internal<
+    const K: usize,
+    const ETA1_64: usize,
+    const ETA2_64: usize,
+    B: Sha3Backend,
+>(
+    m: &[u8; 32], du: u32, dv: u32, ek: &[u8], ct: &mut [u8],
 ) -> Result<SharedSecretKey, &'static str> {
     debug_assert_eq!(ek.len(), 384 * K + 32, "Alg 16: ek len not 384 * K + 32"); // also: size check at top level
     debug_assert_eq!(
@@ -75,17 +129,12 @@ pub(crate) fn ml_kem_encaps<const K: usize, const ETA1_64: usize, const ETA2_64:
         "Alg 16: ek fails modulus check"
     );
 
-    // 1: m ←− B32          ▷ m is 32 random bytes (see Section 3.3)
-    let mut m = [0u8; 32];
-    rng.try_fill_bytes(&mut m)
-        .map_err(|_| "Alg16: random number generator failed")?;
-
     // 2: (K, r) ← G(m∥H(ek))    ▷ derive shared secret key K and randomness r
-    let h_ek = h(ek);
-    let (k, r) = g(&[&m, &h_ek]);
+    let h_ek = h::<B>(ek);
+    let (k, r) = g::<B>(&[m, &h_ek]);
 
     // 3: c ← K-PKE.Encrypt(ek, m, r)    ▷ encrypt m using K-PKE with randomness r
-    k_pke_encrypt::<K, ETA1_64, ETA2_64>(du, dv, ek, &m, &r, ct)?;
+    k_pke_encrypt::<K, ETA1_64, ETA2_64, B>(du, dv, ek, m, &r, ct)?;
 
     // 4: return (K, c)  (note: ct is mutable input)
     Ok(SharedSecretKey(k))
@@ -105,6 +154,7 @@ pub(crate) fn ml_kem_decaps<
     const ETA2_64: usize,
     const J_LEN: usize,
     const CT_LEN: usize,
+    B: Sha3Backend,
 >(
     du: u32, dv: u32, dk: &[u8], ct: &[u8],
 ) -> Result<SharedSecretKey, &'static str> {
@@ -126,20 +176,27 @@ pub(crate) fn ml_kem_decaps<
     let h = &dk[768 * K + 32..768 * K + 64];
 
     // 4: z ← dk[768k + 64 : 768k + 96]    ▷ extract implicit rejection value
-    let z = &dk[768 * K + 64..768 * K + 96];
+    #[cfg_attr(not(feature = "zeroize-internals"), allow(unused_mut))]
+    let mut z: [u8; 32] = dk[768 * K + 64..768 * K + 96].try_into().unwrap();
 
     // 5: m′ ← K-PKE.Decrypt(dkPKE,c)
-    let m_prime = k_pke_decrypt::<K>(du, dv, dk_pke, ct)?;
+    #[cfg_attr(not(feature = "zeroize-internals"), allow(unused_mut))]
+    let mut m_prime = k_pke_decrypt::<K>(du, dv, dk_pke, ct)?;
 
     // 6: (K′, r′) ← G(m′ ∥ h)
-    let (mut k_prime, r_prime) = g(&[&m_prime, h]);
+    #[cfg_attr(not(feature = "zeroize-internals"), allow(unused_mut))]
+    let (mut k_prime, mut r_prime) = g::<B>(&[&m_prime, h]);
 
     // 7: K̄ ← J(z∥c, 32)
-    let k_bar = j(z.try_into().unwrap(), ct);
+    #[cfg_attr(not(feature = "zeroize-internals"), allow(unused_mut))]
+    let mut k_bar = j::<B>(&z, ct);
+    // z is not needed past this point.
+    #[cfg(feature = "zeroize-internals")]
+    z.zeroize();
 
     // 8: c′ ← K-PKE.Encrypt(ekPKE , m′ , r′ )    ▷ re-encrypt using the derived randomness r′
     let mut c_prime = [0u8; CT_LEN];
-    k_pke_encrypt::<K, ETA1_64, ETA2_64>(
+    k_pke_encrypt::<K, ETA1_64, ETA2_64, B>(
         du,
         dv,
         ek_pke,
@@ -147,8 +204,17 @@ pub(crate) fn ml_kem_decaps<
         &r_prime,
         &mut c_prime[0..ct.len()],
     )?;
+    // m′ and r′ are not needed past this point.
+    #[cfg(feature = "zeroize-internals")]
+    {
+        m_prime.zeroize();
+        r_prime.zeroize();
+    }
 
     k_prime.conditional_assign(&k_bar, ct.ct_ne(&c_prime));
+    // k_bar is not needed past this point; k_prime (the real output) is returned below.
+    #[cfg(feature = "zeroize-internals")]
+    k_bar.zeroize();
 
     Ok(SharedSecretKey(k_prime))
 }
@@ -158,6 +224,7 @@ pub(crate) fn ml_kem_decaps<
 mod tests {
     use rand_core::SeedableRng;
 
+    use crate::backend::DefaultSha3Backend;
     use crate::ml_kem::{ml_kem_decaps, ml_kem_encaps, ml_kem_key_gen};
 
     const ETA1: u32 = 3;
@@ -180,13 +247,14 @@ mod tests {
         let mut dk = [0u8; DK_LEN];
         let mut ct = [0u8; CT_LEN];
 
-        let res = ml_kem_key_gen::<K, ETA1_64>(&mut rng, &mut ek, &mut dk);
+        let res = ml_kem_key_gen::<K, ETA1_64, DefaultSha3Backend>(&mut rng, &mut ek, &mut dk);
         assert!(res.is_ok());
 
-        let res = ml_kem_encaps::<K, ETA1_64, ETA2_64>(&mut rng, DU, DV, &ek, &mut ct);
+        let res = ml_kem_encaps::<K, ETA1_64, ETA2_64, DefaultSha3Backend>(&mut rng, DU, DV, &ek, &mut ct);
         assert!(res.is_ok());
 
-        let res = ml_kem_decaps::<K, ETA1_64, ETA2_64, J_LEN, CT_LEN>(DU, DV, &dk, &ct);
+        let res =
+            ml_kem_decaps::<K, ETA1_64, ETA2_64, J_LEN, CT_LEN, DefaultSha3Backend>(DU, DV, &dk, &ct);
         assert!(res.is_ok());
     }
 }
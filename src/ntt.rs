@@ -1,3 +1,13 @@
+//! `ntt`/`ntt_inv`/`multiply_ntts` each still take and return one 256-coefficient polynomial.
+//! [`crate::helpers::mul_mat_vec`] and its two siblings now batch at the level *above* this --
+//! summing a row/column's `K` already-computed [`multiply_ntts`] outputs with one widened `u32`
+//! reduction per coefficient instead of one per term, see [`crate::types::PolyVec`] and
+//! [`crate::types::Z::reduce_wide`] -- rather than changing these three functions themselves to
+//! take a whole `PolyVec` and loop internally, which would just move the same per-term work
+//! inside this module without removing any of it (each of the `K` polynomials in a row still
+//! needs its own independent NTT butterfly network / base-case multiply).
+
+use crate::ntt_backend::{select_kernel, Kernel};
 use crate::types::Z;
 use crate::{Q, ZETA};
 
@@ -5,14 +15,32 @@ use crate::{Q, ZETA};
 /// Algorithm 8 `NTT(f)` on page 22.
 /// Computes the NTT representation `f_hat` of the given polynomial f ∈ `R_q`.
 ///
-/// Input: array `f` ∈ `Z^{256}_q`    ▷ the coefficients of the input polynomial <br>
-/// Output: array `f_hat` ∈ `Z^{256}_q`    ▷ the coefficients of the NTT of the input polynomial
+/// Dispatches to the [`Kernel`] selected for this build; see `ntt_backend` for why every
+/// variant currently runs the same portable implementation as [`Kernel::Portable`].
 #[must_use]
 #[allow(clippy::module_name_repetitions)]
 pub(crate) fn ntt(array_f: &[Z; 256]) -> [Z; 256] {
+    match select_kernel() {
+        Kernel::Portable | Kernel::Sse2 | Kernel::Avx2 | Kernel::Neon => ntt_portable(array_f),
+    }
+}
+
+
+/// Portable scalar kernel behind [`ntt`].
+///
+/// Input: array `f` ∈ `Z^{256}_q`    ▷ the coefficients of the input polynomial <br>
+/// Output: array `f_hat` ∈ `Z^{256}_q`    ▷ the coefficients of the NTT of the input polynomial
+///
+/// Runs the butterflies in Montgomery form: `f` is converted in once at entry (`to_montgomery`),
+/// every `zeta` comes from [`ZETA_TABLE_MONT`] (the Montgomery form of [`ZETA_TABLE`], computed
+/// at compile time alongside it) and is applied via [`Z::mul_montgomery`], and the result is
+/// converted back out once at exit (`from_montgomery`) -- so this returns the exact same ordinary
+/// `Z_q` values as before, just via `montgomery_reduce` instead of Barrett reduction per multiply.
+#[must_use]
+fn ntt_portable(array_f: &[Z; 256]) -> [Z; 256] {
     //
-    // 1: f_hat ← f    ▷ will compute NTT in-place on a copy of input array
-    let mut f_hat: [Z; 256] = core::array::from_fn(|i| array_f[i]);
+    // 1: f_hat ← f    ▷ will compute NTT in-place on a copy of input array, in Montgomery form
+    let mut f_hat: [Z; 256] = core::array::from_fn(|i| array_f[i].to_montgomery());
 
     // 2: k ← 1
     let mut k = 1;
@@ -23,9 +51,9 @@ pub(crate) fn ntt(array_f: &[Z; 256]) -> [Z; 256] {
         // 4: for (start ← 0; start < 256; start ← start + 2 · len)
         for start in (0..256).step_by(2 * len) {
             //
-            // 5: zeta ← ζ^{BitRev7 (k)} mod q
+            // 5: zeta ← ζ^{BitRev7 (k)} mod q, in Montgomery form
             let mut zeta = Z::default();
-            zeta.set_u16(ZETA_TABLE[k << 1]);
+            zeta.set_u16(ZETA_TABLE_MONT[k << 1]);
 
             // 6: k ← k+1
             k += 1;
@@ -34,7 +62,7 @@ pub(crate) fn ntt(array_f: &[Z; 256]) -> [Z; 256] {
             for j in start..(start + len) {
                 //
                 // 8: t ← zeta · f_hat[ j + len]    ▷ steps 8-10 done modulo q
-                let t = f_hat[j + len].mul(zeta);
+                let t = f_hat[j + len].mul_montgomery(zeta);
 
                 // 9: f_hat[ j + len] ← f_hat [ j] − t
                 f_hat[j + len] = f_hat[j].sub(t);
@@ -51,21 +79,35 @@ pub(crate) fn ntt(array_f: &[Z; 256]) -> [Z; 256] {
         // 13: end for
     }
 
-    // 14: return f_hat
-    f_hat
+    // 14: return f_hat, converted back out of Montgomery form
+    core::array::from_fn(|i| f_hat[i].from_montgomery())
 }
 
 
 /// Algorithm 9 `NTTinv(f)` on page 23.
 /// Computes the polynomial `f` ∈ `R_q` corresponding to the given NTT representation `f_hat` ∈ `T_q`.
 ///
-/// Input: array `f_hat` ∈ `Z^{256}`    ▷ the coefficients of input NTT representation <br>
-/// Output: array `f` ∈ `Z^{256}`    ▷ the coefficients of the inverse-NTT of the input
+/// Dispatches to the [`Kernel`] selected for this build; see `ntt_backend` for why every
+/// variant currently runs the same portable implementation as [`Kernel::Portable`].
 #[must_use]
 #[allow(clippy::module_name_repetitions)]
 pub(crate) fn ntt_inv(f_hat: &[Z; 256]) -> [Z; 256] {
-    // 1: f ← f_hat    ▷ will compute in-place on a copy of input array
-    let mut f: [Z; 256] = core::array::from_fn(|i| f_hat[i]);
+    match select_kernel() {
+        Kernel::Portable | Kernel::Sse2 | Kernel::Avx2 | Kernel::Neon => ntt_inv_portable(f_hat),
+    }
+}
+
+
+/// Portable scalar kernel behind [`ntt_inv`].
+///
+/// Input: array `f_hat` ∈ `Z^{256}`    ▷ the coefficients of input NTT representation <br>
+/// Output: array `f` ∈ `Z^{256}`    ▷ the coefficients of the inverse-NTT of the input
+///
+/// Kept in Montgomery form throughout, the same way [`ntt_portable`] is -- see its doc comment.
+#[must_use]
+fn ntt_inv_portable(f_hat: &[Z; 256]) -> [Z; 256] {
+    // 1: f ← f_hat    ▷ will compute in-place on a copy of input array, in Montgomery form
+    let mut f: [Z; 256] = core::array::from_fn(|i| f_hat[i].to_montgomery());
 
     // 2: k ← 127
     let mut k = 127;
@@ -76,9 +118,9 @@ pub(crate) fn ntt_inv(f_hat: &[Z; 256]) -> [Z; 256] {
         // 4: for (start ← 0; start < 256; start ← start + 2 · len)
         for start in (0..256).step_by(2 * len) {
             //
-            // 5: zeta ← ζ^{BitRev7(k)} mod q
+            // 5: zeta ← ζ^{BitRev7(k)} mod q, in Montgomery form
             let mut zeta = Z::default();
-            zeta.set_u16(ZETA_TABLE[k << 1]);
+            zeta.set_u16(ZETA_TABLE_MONT[k << 1]);
 
             // 6: k ← k − 1
             k -= 1;
@@ -93,7 +135,7 @@ pub(crate) fn ntt_inv(f_hat: &[Z; 256]) -> [Z; 256] {
                 f[j] = t.add(f[j + len]);
 
                 // 10: f [ j + len] ← zeta · ( f [ j + len] − t)
-                f[j + len] = zeta.mul(f[j + len].sub(t));
+                f[j + len] = zeta.mul_montgomery(f[j + len].sub(t));
 
                 // 11: end for
             }
@@ -107,20 +149,39 @@ pub(crate) fn ntt_inv(f_hat: &[Z; 256]) -> [Z; 256] {
     // 14: f ← f · 3303 mod q    ▷ multiply every entry by 3303 ≡ 128^{−1} mod q
     let mut z3303 = Z::default();
     z3303.set_u16(3303);
-    f.iter_mut().for_each(|item| *item = item.mul(z3303));
+    let z3303_mont = z3303.to_montgomery();
+    f.iter_mut().for_each(|item| *item = item.mul_montgomery(z3303_mont));
 
-    // 15: return f
-    f
+    // 15: return f, converted back out of Montgomery form
+    core::array::from_fn(|i| f[i].from_montgomery())
 }
 
 
 /// Algorithm 10 `MultiplyNTTs(f, g)` on page 24.
 /// Computes the product (in the ring `T_q` ) of two NTT representations.
 ///
+/// Dispatches to the [`Kernel`] selected for this build; see `ntt_backend` for why every
+/// variant currently runs the same portable implementation as [`Kernel::Portable`].
+#[must_use]
+pub(crate) fn multiply_ntts(f_hat: &[Z; 256], g_hat: &[Z; 256]) -> [Z; 256] {
+    match select_kernel() {
+        Kernel::Portable | Kernel::Sse2 | Kernel::Avx2 | Kernel::Neon => {
+            multiply_ntts_portable(f_hat, g_hat)
+        }
+    }
+}
+
+
+/// Portable scalar kernel behind [`multiply_ntts`].
+///
 /// Input: Two arrays `f_hat` ∈ `Z^{256}_q` and `g_hat` ∈ `Z^{256}_q`    ▷ the coefficients of two NTT representations <br>
 /// Output: An array `h_hat` ∈ `Z^{256}_q`    ▷ the coefficients of the product of the inputs
+///
+/// Converts each base-case pair into Montgomery form before [`base_case_multiply`] (which now
+/// multiplies via [`Z::mul_montgomery`]) and converts `h_hat` back out at the end -- same
+/// Montgomery-domain-through-the-hot-path approach as [`ntt_portable`]/[`ntt_inv_portable`].
 #[must_use]
-pub(crate) fn multiply_ntts(f_hat: &[Z; 256], g_hat: &[Z; 256]) -> [Z; 256] {
+fn multiply_ntts_portable(f_hat: &[Z; 256], g_hat: &[Z; 256]) -> [Z; 256] {
     let mut h_hat: [Z; 256] = [Z::default(); 256];
 
     // for (i ← 0; i < 128; i ++)
@@ -128,11 +189,16 @@ pub(crate) fn multiply_ntts(f_hat: &[Z; 256], g_hat: &[Z; 256]) -> [Z; 256] {
         //
         // 2: (h_hat[2i], h_hat[2i + 1]) ← BaseCaseMultiply( f_hat[2i], f_hat[2i + 1], g_hat[2i], g_hat[2i + 1], ζ^{2BitRev7(i) + 1})
         let mut zt = Z::default();
-        zt.set_u16(ZETA_TABLE[i ^ 0x80]);
-        let (h_hat_2i, h_hat_2ip1) =
-            base_case_multiply(f_hat[2 * i], f_hat[2 * i + 1], g_hat[2 * i], g_hat[2 * i + 1], zt);
-        h_hat[2 * i] = h_hat_2i;
-        h_hat[2 * i + 1] = h_hat_2ip1;
+        zt.set_u16(ZETA_TABLE_MONT[i ^ 0x80]);
+        let (h_hat_2i, h_hat_2ip1) = base_case_multiply(
+            f_hat[2 * i].to_montgomery(),
+            f_hat[2 * i + 1].to_montgomery(),
+            g_hat[2 * i].to_montgomery(),
+            g_hat[2 * i + 1].to_montgomery(),
+            zt,
+        );
+        h_hat[2 * i] = h_hat_2i.from_montgomery();
+        h_hat[2 * i + 1] = h_hat_2ip1.from_montgomery();
 
         // 3: end for
     }
@@ -145,16 +211,18 @@ pub(crate) fn multiply_ntts(f_hat: &[Z; 256], g_hat: &[Z; 256]) -> [Z; 256] {
 /// Algorithm 11 `BaseCaseMultiply(a0, a1, b0, b1, gamma)` on page 24.
 /// Computes the product of two degree-one polynomials with respect to a quadratic modulus.
 ///
-/// Input: `a0`, `a1`, `b0`, `b1` ∈ `Z_q`    ▷ the coefficients of `a0` + `a1` X and `b0` + `b1` X
-/// Input: `γ` ∈ `Z_q`    ▷ the modulus is `X^2 − γ`
-/// Output: `c0`, `c1` ∈ `Z_q`    ▷ the coefficients of the product of the two polynomials
+/// Input: `a0`, `a1`, `b0`, `b1`, `γ` ∈ `Z_q`, all in Montgomery form    ▷ the modulus is `X^2 − γ`
+/// Output: `c0`, `c1` ∈ `Z_q`, in Montgomery form    ▷ the coefficients of the product
+///
+/// Only called from [`multiply_ntts_portable`], which handles the Montgomery conversion at its
+/// own entry/exit; every multiply below is [`Z::mul_montgomery`] rather than Barrett reduction.
 #[must_use]
 pub(crate) fn base_case_multiply(a0: Z, a1: Z, b0: Z, b1: Z, gamma: Z) -> (Z, Z) {
     // 1: c0 ← a0 · b0 + a1 · b1 · γ    ▷ steps 1-2 done modulo q
-    let c0 = a0.mul(b0).add(a1.mul(b1).mul(gamma));
+    let c0 = a0.mul_montgomery(b0).add(a1.mul_montgomery(b1).mul_montgomery(gamma));
 
     // 2: 2: c1 ← a0 · b1 + a1 · b0
-    let c1 = a0.mul(b1).add(a1.mul(b0));
+    let c1 = a0.mul_montgomery(b1).add(a1.mul_montgomery(b0));
 
     // 3: return c0 , c1
     (c0, c1)
@@ -180,6 +248,26 @@ const fn gen_zeta_table() -> [u16; 256] {
 
 pub(crate) static ZETA_TABLE: [u16; 256] = gen_zeta_table();
 
+/// [`ZETA_TABLE`], converted into Montgomery form (`zeta * R mod Q`, `R = 2^16`) at compile time
+/// so the hot-path butterflies in [`ntt_portable`]/[`ntt_inv_portable`]/[`multiply_ntts_portable`]
+/// can multiply by a `zeta` with [`Z::mul_montgomery`] directly, without a per-butterfly
+/// conversion. Computed with the same plain `u32`/`%` arithmetic `gen_zeta_table` uses (rather
+/// than calling the non-`const` [`Z::to_montgomery`]) since `const fn` can't call it.
+#[must_use]
+#[allow(clippy::cast_possible_truncation)] // const fns cannot use u32::from() etc...
+const fn gen_zeta_table_montgomery() -> [u16; 256] {
+    let base = gen_zeta_table();
+    let mut result = [0u16; 256];
+    let mut i = 0usize;
+    while i < 256 {
+        result[i] = (((base[i] as u32) << 16) % (Q as u32)) as u16;
+        i += 1;
+    }
+    result
+}
+
+pub(crate) static ZETA_TABLE_MONT: [u16; 256] = gen_zeta_table_montgomery();
+
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,117 @@
+//! Backend selection for the NTT/`NTT^-1`/`MultiplyNTTs` kernels in [`crate::ntt`].
+//!
+//! Real per-ISA kernels (SSE2/AVX2/NEON intrinsics operating on packed lanes instead of one
+//! `Z` at a time) are the obvious way to speed this hot path up, the way `curve25519-dalek`'s
+//! backend crate picks an `avx2`/`ifma`/`serial` field implementation. [`Kernel`] exists as the
+//! real selection/dispatch surface for that (compile-time `cfg` selection under `no_std`,
+//! `is_x86_feature_detected!`/`is_aarch64_feature_detected!` runtime probing cached behind a
+//! [`std::sync::OnceLock`] when the `std` feature is enabled) — but every non-[`Kernel::Portable`]
+//! variant currently dispatches to the same safe scalar kernel as `Portable`. No SSE2/AVX2/NEON
+//! kernel has actually been implemented yet.
+//!
+//! This is not because this crate's `#![deny(unsafe_code)]` makes it impossible: `deny`, unlike
+//! `forbid`, is a default that a local `#[allow(unsafe_code)]` can override, the same pattern
+//! `curve25519-dalek`'s own `u64`/`avx2`/`ifma` backends use to scope `unsafe` to the handful of
+//! functions that actually need `core::arch` intrinsics. A real kernel here would follow the same
+//! shape: a local `#[allow(unsafe_code)]` around the `#[target_feature]` functions calling
+//! `_mm256_add_epi16`-style (AVX2) or `vaddq_u16`-style (NEON) intrinsics, with the safe match in
+//! [`crate::ntt`] as the only other thing that would need to change.
+//!
+//! The `force-portable`/`force-sse2`/`force-avx2`/`force-neon` features override detection so
+//! that CI can run the same NIST test vectors once per forced [`Kernel`], exercising the
+//! selection/dispatch plumbing ahead of a real kernel landing behind it — today that just means
+//! every forced variant runs the identical portable kernel, not that distinct vector code is
+//! being compared.
+
+/// Which kernel [`crate::ntt::ntt`]/[`crate::ntt::ntt_inv`]/[`crate::ntt::multiply_ntts`] should
+/// use. See the module docs for why every variant but [`Kernel::Portable`] is currently a named
+/// placeholder rather than a distinct vectorized implementation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Kernel {
+    /// Portable scalar kernel; always available, and currently what every other variant runs.
+    Portable,
+    /// x86_64 SSE2 kernel (reserved; see module docs).
+    Sse2,
+    /// x86_64 AVX2 kernel (reserved; see module docs).
+    Avx2,
+    /// `aarch64` NEON kernel (reserved; see module docs).
+    Neon,
+}
+
+/// `force-portable` override: always report [`Kernel::Portable`], regardless of target/detection.
+#[cfg(feature = "force-portable")]
+pub(crate) fn select_kernel() -> Kernel { Kernel::Portable }
+
+/// `force-sse2` override: always report [`Kernel::Sse2`], regardless of target/detection.
+#[cfg(feature = "force-sse2")]
+pub(crate) fn select_kernel() -> Kernel { Kernel::Sse2 }
+
+/// `force-avx2` override: always report [`Kernel::Avx2`], regardless of target/detection.
+#[cfg(feature = "force-avx2")]
+pub(crate) fn select_kernel() -> Kernel { Kernel::Avx2 }
+
+/// `force-neon` override: always report [`Kernel::Neon`], regardless of target/detection.
+#[cfg(feature = "force-neon")]
+pub(crate) fn select_kernel() -> Kernel { Kernel::Neon }
+
+/// No forced override: detect the kernel for this build (compile-time under `no_std`, cached
+/// runtime probing when the `std` feature is enabled).
+#[cfg(not(any(
+    feature = "force-portable",
+    feature = "force-sse2",
+    feature = "force-avx2",
+    feature = "force-neon",
+)))]
+pub(crate) fn select_kernel() -> Kernel { detect() }
+
+/// `no_std` build: the target's available instruction set is fixed at compile time, so the
+/// choice is a `const` one keyed off `target_feature` — no runtime cost, nothing to cache.
+#[cfg(not(feature = "std"))]
+const fn detect() -> Kernel {
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+    {
+        Kernel::Avx2
+    }
+    #[cfg(all(target_arch = "x86_64", target_feature = "sse2", not(target_feature = "avx2")))]
+    {
+        Kernel::Sse2
+    }
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        Kernel::Neon
+    }
+    #[cfg(not(any(
+        all(target_arch = "x86_64", target_feature = "sse2"),
+        all(target_arch = "x86_64", target_feature = "avx2"),
+        all(target_arch = "aarch64", target_feature = "neon"),
+    )))]
+    {
+        Kernel::Portable
+    }
+}
+
+/// `std` build: probe the live CPU once and cache the result, so a binary built for a baseline
+/// target (no `target-feature` / `target-cpu` flags) can still pick up a wider kernel on
+/// hardware that actually has it, the way `is_x86_feature_detected!` is meant to be used.
+#[cfg(feature = "std")]
+fn detect() -> Kernel {
+    static CACHED: std::sync::OnceLock<Kernel> = std::sync::OnceLock::new();
+    *CACHED.get_or_init(|| {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if std::is_x86_feature_detected!("avx2") {
+                return Kernel::Avx2;
+            }
+            if std::is_x86_feature_detected!("sse2") {
+                return Kernel::Sse2;
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::is_aarch64_feature_detected!("neon") {
+                return Kernel::Neon;
+            }
+        }
+        Kernel::Portable
+    })
+}
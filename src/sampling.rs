@@ -3,17 +3,31 @@ use crate::Q;
 use sha3::digest::XofReader;
 
 
+/// Number of 3-byte/2-candidate groups drawn from the XOF per batch in [`sample_ntt`]. Chosen
+/// so the per-batch decode and compare passes are long enough for the compiler to auto-vectorize
+/// on platforms where that helps, while keeping the stack buffers small.
+const SAMPLE_NTT_BATCH_TRIPLES: usize = 16; // 48 bytes in, up to 32 candidates out, per batch
+
 /// Algorithm 6 `SampleNTT(B)` on page 20.
 /// If the input is a stream of uniformly random bytes, the output is a uniformly random element of `T_q`.
 ///
 /// Input: byte stream B ∈ B^{∗} <br>
 /// Output: array `a_hat` ∈ `Z^{256}_q`              ▷ the coefficients of the NTT of a polynomial
+///
+/// This draws bytes from the XOF in batches of [`SAMPLE_NTT_BATCH_TRIPLES`] triples rather than
+/// one triple at a time: decoding a whole batch of 12-bit candidates up front, then testing and
+/// compacting them against `q` in a second pass, gives the compiler two long straight-line loops
+/// to auto-vectorize instead of one short branchy one. A hand-written AVX2/NEON path was
+/// considered but is not used, since both require `unsafe` and this crate denies `unsafe_code`
+/// crate-wide; the batching here is the safe-Rust equivalent of the vector-compare-and-compress
+/// shape such a path would take.
 pub(crate) fn sample_ntt(mut byte_stream_b: impl XofReader) -> [Z; 256] {
     //
     let mut array_a_hat = [Z::default(); 256];
-    let mut bbb = [0u8; 3]; // Space for 3 random (byte) draws
+    let mut block = [0u8; 3 * SAMPLE_NTT_BATCH_TRIPLES];
+    let mut candidates = [0u32; 2 * SAMPLE_NTT_BATCH_TRIPLES];
 
-    // 1: i ← 0 (not needed as three bytes are repeatedly drawn from the rng bytestream via bbb)
+    // 1: i ← 0 (not needed as bytes are repeatedly drawn from the rng bytestream via block)
 
     // 2: j ← 0
     let mut j = 0usize;
@@ -21,43 +35,38 @@ pub(crate) fn sample_ntt(mut byte_stream_b: impl XofReader) -> [Z; 256] {
     // This rejection sampling loop is solely dependent upon rho which crosses a trust boundary
     // in the clear. Thus, it does not need to be constant time.
     // 3: while j < 256 do
-    #[allow(clippy::cast_possible_truncation)] // d1 as u16, d2 as u16
+    #[allow(clippy::cast_possible_truncation)] // d as u16
     while j < 256 {
-        //
-        // Note: two samples (d1, d2) are drawn from these per loop iteration
-        byte_stream_b.read(&mut bbb); // Draw 3 bytes
-
-        // 4: d1 ← B[i] + 256 · (B[i + 1] mod 16)
-        let d1 = u32::from(bbb[0]) + 256 * (u32::from(bbb[1]) & 0x0F);
-
-        // 5: d2 ← ⌊B[i + 1]/16⌋ + 16 · B[i + 2]
-        let d2 = (u32::from(bbb[1]) >> 4) + 16 * u32::from(bbb[2]);
+        // Draw a whole batch of 3-byte groups at once.
+        byte_stream_b.read(&mut block);
 
-        // 6: if d1 < q then
-        if d1 < u32::from(Q) {
-            //
-            // 7: a_hat[j] ← d1         ▷ a_hat ∈ Z256
-            array_a_hat[j].set_u16(d1 as u16);
+        // 4/5: decode every (d1, d2) pair in the batch, straight-line (no rejection yet).
+        for (triple, pair) in block.chunks_exact(3).zip(candidates.chunks_exact_mut(2)) {
+            // 4: d1 ← B[i] + 256 · (B[i + 1] mod 16)
+            pair[0] = u32::from(triple[0]) + 256 * (u32::from(triple[1]) & 0x0F);
 
-            // 8: j ← j+1
-            j += 1;
-
-            // 9: end if
+            // 5: d2 ← ⌊B[i + 1]/16⌋ + 16 · B[i + 2]
+            pair[1] = (u32::from(triple[1]) >> 4) + 16 * u32::from(triple[2]);
         }
 
-        // 10: if d2 < q and j < 256 then
-        if (d2 < u32::from(Q)) & (j < 256) {
-            //
-            // 11: a_hat[j] ← d2
-            array_a_hat[j].set_u16(d2 as u16);
-
-            // 12: j ← j+1
-            j += 1;
-
-            // 13: end if
+        // 6-13: reject candidates >= q and compact the survivors of this batch into a_hat,
+        // advancing j by however many of the batch were accepted.
+        for &d in &candidates {
+            // 10 (j < 256 guard, checked here for both d1 and d2 uniformly)
+            if j == 256 {
+                break;
+            }
+            // 6/10: if d < q then
+            if d < u32::from(Q) {
+                // 7/11: a_hat[j] ← d         ▷ a_hat ∈ Z256
+                array_a_hat[j].set_u16(d as u16);
+
+                // 8/12: j ← j+1
+                j += 1;
+            }
         }
 
-        // 14: i ← i+3  (not needed as we draw 3 more bytes next time
+        // 14: i ← i+3 per triple (not needed as we draw a fresh batch next time)
 
         // 15: end while
     }
@@ -127,3 +136,54 @@ fn count_ones(x: u32) -> u16 {
 // 6: end for
 // 7: return f
 // }
+
+
+#[cfg(test)]
+mod tests {
+    extern crate alloc;
+
+    use alloc::vec::Vec;
+
+    use sha3::digest::{ExtendableOutput, Update, XofReader};
+    use sha3::Shake128;
+
+    use crate::sampling::sample_ntt;
+    use crate::types::Z;
+    use crate::Q;
+
+    /// Unbatched, one-triple-at-a-time reference reimplementation of Algorithm 6, kept only to
+    /// confirm the batched `sample_ntt` above is equivalent for the same XOF stream.
+    #[allow(clippy::cast_possible_truncation)]
+    fn reference_sample_ntt(mut byte_stream_b: impl XofReader) -> [Z; 256] {
+        let mut array_a_hat = [Z::default(); 256];
+        let mut bbb = [0u8; 3];
+        let mut j = 0usize;
+        while j < 256 {
+            byte_stream_b.read(&mut bbb);
+            let d1 = u32::from(bbb[0]) + 256 * (u32::from(bbb[1]) & 0x0F);
+            let d2 = (u32::from(bbb[1]) >> 4) + 16 * u32::from(bbb[2]);
+            if d1 < u32::from(Q) {
+                array_a_hat[j].set_u16(d1 as u16);
+                j += 1;
+            }
+            if (d2 < u32::from(Q)) & (j < 256) {
+                array_a_hat[j].set_u16(d2 as u16);
+                j += 1;
+            }
+        }
+        array_a_hat
+    }
+
+    #[test]
+    fn test_batched_matches_reference() {
+        for seed in 0u8..8 {
+            let mut hasher = Shake128::default();
+            Update::update(&mut hasher, &[seed]);
+            let batched = sample_ntt(hasher.clone().finalize_xof());
+            let reference = reference_sample_ntt(hasher.finalize_xof());
+            let batched_u16: Vec<_> = batched.iter().map(|z| z.get_u16()).collect();
+            let reference_u16: Vec<_> = reference.iter().map(|z| z.get_u16()).collect();
+            assert_eq!(batched_u16, reference_u16);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Integrated KEM-DEM authenticated encryption: `seal`/`open` a message directly against an
+//! `EncapsKey`/`DecapsKey`, without the caller deriving an AEAD key or assembling a blob by hand.
+//! Requires the `seal` feature.
+//!
+//! Differs from [`crate::hybrid`] (single concatenated blob) and [`crate::hpke`] (RFC 9180
+//! multi-message `Context`): both of those derive their AEAD key via HKDF-SHA256, while [`seal`]
+//! expands the `SharedSecretKey` into an AEAD key and nonce with a single SHAKE256 squeeze
+//! (domain-separated by [`EXPAND_LABEL`]), matching how the rest of FIPS 203's own symmetric
+//! primitives already lean on SHAKE/SHA3 rather than pulling in a separate KDF. [`seal`] returns
+//! the KEM ciphertext and AEAD ciphertext as two separate values rather than one blob, and
+//! [`open`] takes them back as two arguments.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use rand_core::CryptoRngCore;
+
+use crate::aead_dispatch::AeadError;
+pub use crate::aead_dispatch::AeadAlg;
+use crate::backend::{DefaultSha3Backend, Sha3Backend};
+use crate::traits::{Decaps, Encaps};
+use crate::SharedSecretKey;
+
+const EXPAND_LABEL: &[u8] = b"fips203-seal-aead-key-nonce-v1";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+
+/// Maps the AEAD-dispatch error shared with [`crate::hpke`] onto this module's own
+/// `"seal: ..."`-prefixed messages.
+fn map_err(e: AeadError) -> &'static str {
+    match e {
+        AeadError::BadKeyLength(AeadAlg::Aes256Gcm) => "seal: bad AES-256-GCM key length",
+        AeadError::BadKeyLength(AeadAlg::ChaCha20Poly1305) => "seal: bad ChaCha20Poly1305 key length",
+        AeadError::SealFailed => "seal: AEAD seal failed",
+        AeadError::OpenFailed => "seal: AEAD open failed",
+    }
+}
+
+
+/// Expands `shared_secret` into an AEAD key and nonce with one SHAKE256 squeeze, domain
+/// separated by [`EXPAND_LABEL`] so this never collides with any other use of the shared secret.
+fn derive_key_nonce(shared_secret: &[u8; 32]) -> ([u8; KEY_LEN], [u8; NONCE_LEN]) {
+    let mut reader = DefaultSha3Backend::shake256(&[shared_secret, EXPAND_LABEL]);
+    let mut key = [0u8; KEY_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    reader.read(&mut key);
+    reader.read(&mut nonce);
+    (key, nonce)
+}
+
+
+/// Encapsulates to `ek` and seals `plaintext` (authenticating `aad`) under `aead_alg`. Returns
+/// the KEM ciphertext and the AEAD ciphertext (including tag) as two separate values.
+/// # Errors
+/// Returns an error if the KEM, the random number generator, or the AEAD step fails.
+pub fn seal<E>(
+    ek: &E, aad: &[u8], plaintext: &[u8], aead_alg: AeadAlg, rng: &mut impl CryptoRngCore,
+) -> Result<(E::CipherText, Vec<u8>), &'static str>
+where E: Encaps<SharedSecretKey = SharedSecretKey> {
+    let (ssk, ct_kem) = ek.try_encaps_with_rng(rng)?;
+    let (key, nonce) = derive_key_nonce(&ssk.into_bytes());
+    let ct_aead = aead_alg.seal(&key, &nonce, aad, plaintext).map_err(map_err)?;
+    Ok((ct_kem, ct_aead))
+}
+
+
+/// Reverses [`seal`]: decapsulates `ct_kem` with `dk`, re-derives the same AEAD key and nonce,
+/// and opens `ct_aead` (checking `aad`). A failed tag check returns an error without branching
+/// on any secret data (the AEAD crate's own tag comparison is constant-time).
+/// # Errors
+/// Returns an error if the KEM fails or authentication fails.
+pub fn open<D>(
+    dk: &D, aad: &[u8], ct_kem: &D::CipherText, ct_aead: &[u8], aead_alg: AeadAlg,
+) -> Result<Vec<u8>, &'static str>
+where D: Decaps<SharedSecretKey = SharedSecretKey> {
+    let ssk = dk.try_decaps(ct_kem)?;
+    let (key, nonce) = derive_key_nonce(&ssk.into_bytes());
+    aead_alg.open(&key, &nonce, aad, ct_aead).map_err(map_err)
+}
+
+
+#[cfg(all(test, feature = "ml-kem-512"))]
+mod tests {
+    use rand_core::SeedableRng;
+
+    use super::{open, seal, AeadAlg};
+    use crate::ml_kem_512::KG;
+    use crate::traits::KeyGen;
+
+    #[test]
+    fn test_seal_open_roundtrip_multi_kilobyte() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(91);
+        let (ek, dk) = KG::try_keygen_with_rng(&mut rng).unwrap();
+        let plaintext = alloc::vec![0x5au8; 8192];
+        let aad = b"seal-header";
+
+        for aead_alg in [AeadAlg::Aes256Gcm, AeadAlg::ChaCha20Poly1305] {
+            let (ct_kem, ct_aead) = seal(&ek, aad, &plaintext, aead_alg, &mut rng).unwrap();
+            let opened = open(&dk, aad, &ct_kem, &ct_aead, aead_alg).unwrap();
+            assert_eq!(opened, plaintext);
+        }
+    }
+
+    #[test]
+    fn test_open_rejects_tampered_ciphertext() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(92);
+        let (ek, dk) = KG::try_keygen_with_rng(&mut rng).unwrap();
+        let (ct_kem, mut ct_aead) = seal(&ek, b"aad", b"message", AeadAlg::Aes256Gcm, &mut rng).unwrap();
+        let last = ct_aead.len() - 1;
+        ct_aead[last] ^= 0x01;
+        assert!(open(&dk, b"aad", &ct_kem, &ct_aead, AeadAlg::Aes256Gcm).is_err());
+    }
+}
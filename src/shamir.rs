@@ -0,0 +1,196 @@
+//! GF(256) Shamir secret sharing, applied byte-wise to a serialized `DecapsKey`.
+//!
+//! For high-value deployments this lets a decapsulation key be split into `n` shares of
+//! which any `t` can reconstruct it, so the serialized key never exists whole on a single
+//! disk/host. For each secret byte a degree-`(t-1)` polynomial is chosen with that byte as
+//! the constant term and random coefficients; it is evaluated at distinct nonzero
+//! x-coordinates `1..=n` to produce `n` share-bytes. Reconstruction collects any `t` shares
+//! and runs Lagrange interpolation at `x=0` in GF(256) to recover each byte. Callers should
+//! run the recovered bytes through `SerDes::try_from_bytes()` before use, which performs the
+//! usual key validation.
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use rand_core::CryptoRngCore;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::helpers::ensure;
+
+
+/// One share of a byte-wise Shamir-split secret: an x-coordinate (1..=n) and the
+/// corresponding share bytes, which are the same length as the secret being split.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct Share<const LEN: usize> {
+    x: u8,
+    y: [u8; LEN],
+}
+
+impl<const LEN: usize> Share<LEN> {
+    /// The x-coordinate (1..=n) this share was evaluated at.
+    #[must_use]
+    pub fn x(&self) -> u8 { self.x }
+
+    /// The share bytes (the polynomial value at `x`, byte-wise across the secret).
+    #[must_use]
+    pub fn y(&self) -> &[u8; LEN] { &self.y }
+}
+
+
+/// GF(256) multiplication using the AES/Rijndael reduction polynomial `x^8+x^4+x^3+x+1` (0x11B).
+fn gf256_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1B;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+
+/// GF(256) multiplicative inverse via exponentiation (every nonzero element satisfies `a^255 = 1`,
+/// so `a^254 = a^-1`). Undefined (returns 0) for `a == 0`; callers never invoke this at `a == 0`.
+fn gf256_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exponent = 254u8;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = gf256_mul(result, base);
+        }
+        base = gf256_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+
+/// GF(256) division `a / b`, for `b != 0`.
+fn gf256_div(a: u8, b: u8) -> u8 { gf256_mul(a, gf256_inv(b)) }
+
+
+/// Splits a serialized `DecapsKey` (obtained via `SerDes::into_bytes()`) into `n` shares of
+/// which any `t` can reconstruct the original secret.
+/// # Errors
+/// Returns an error if `t` is zero, `t` exceeds `n`, or `n` exceeds 255 (the number of
+/// nonzero points in GF(256)), or if the random number generator fails.
+pub fn split_decaps_key<const LEN: usize>(
+    secret: &[u8; LEN], t: u8, n: u8, rng: &mut impl CryptoRngCore,
+) -> Result<Vec<Share<LEN>>, &'static str> {
+    ensure!(t >= 1, "Shamir: threshold t must be at least 1");
+    ensure!(t <= n, "Shamir: threshold t must not exceed share count n");
+    ensure!(n <= 255, "Shamir: share count n must not exceed 255");
+
+    let mut shares: Vec<Share<LEN>> =
+        (1..=n).map(|x| Share { x, y: [0u8; LEN] }).collect();
+
+    for byte_index in 0..LEN {
+        // Degree t-1 polynomial: coeffs[0] is the secret byte, the rest are random.
+        let mut coeffs = vec![0u8; t as usize];
+        coeffs[0] = secret[byte_index];
+        rng.try_fill_bytes(&mut coeffs[1..]).map_err(|_| "Shamir: random number generator failed")?;
+
+        for share in &mut shares {
+            // Evaluate the polynomial at `share.x` via Horner's method.
+            let mut value = 0u8;
+            for &coeff in coeffs.iter().rev() {
+                value = gf256_mul(value, share.x) ^ coeff;
+            }
+            share.y[byte_index] = value;
+        }
+
+        coeffs.zeroize();
+    }
+
+    Ok(shares)
+}
+
+
+/// Reconstructs a serialized `DecapsKey` from any `t` of the `n` shares produced by
+/// [`split_decaps_key`]. The caller should pass the result through `SerDes::try_from_bytes()`
+/// to validate the recovered key before use.
+/// # Errors
+/// Returns an error if no shares are provided or if two shares share the same x-coordinate
+/// (which would make the interpolation denominator zero).
+pub fn recover_decaps_key<const LEN: usize>(shares: &[Share<LEN>]) -> Result<[u8; LEN], &'static str> {
+    ensure!(!shares.is_empty(), "Shamir: no shares provided");
+
+    let mut secret = [0u8; LEN];
+    for byte_index in 0..LEN {
+        // Lagrange interpolation of the byte_index-th coordinate at x = 0.
+        let mut value = 0u8;
+        for (i, share_i) in shares.iter().enumerate() {
+            let mut numerator = 1u8;
+            let mut denominator = 1u8;
+            for (j, share_j) in shares.iter().enumerate() {
+                if i == j {
+                    continue;
+                }
+                ensure!(share_i.x != share_j.x, "Shamir: duplicate share x-coordinate");
+                // Subtraction is XOR in GF(256): (0 - share_j.x) == share_j.x
+                numerator = gf256_mul(numerator, share_j.x);
+                denominator = gf256_mul(denominator, share_i.x ^ share_j.x);
+            }
+            value ^= gf256_mul(share_i.y[byte_index], gf256_div(numerator, denominator));
+        }
+        secret[byte_index] = value;
+    }
+
+    Ok(secret)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand_core::SeedableRng;
+
+    use super::{gf256_mul, recover_decaps_key, split_decaps_key};
+
+    #[test]
+    fn test_gf256_mul_identity() {
+        for a in 0..=255u8 {
+            assert_eq!(gf256_mul(a, 1), a);
+            assert_eq!(gf256_mul(a, 0), 0);
+        }
+    }
+
+    #[test]
+    fn test_split_and_recover_threshold() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(42);
+        let secret = [0x42u8; 1632]; // ML-KEM-512 DK_LEN, but any LEN works
+        let shares = split_decaps_key(&secret, 3, 5, &mut rng).unwrap();
+
+        // Any 3-of-5 subset reconstructs the secret.
+        let subset1 = [shares[0].clone(), shares[1].clone(), shares[2].clone()];
+        assert_eq!(recover_decaps_key(&subset1).unwrap(), secret);
+
+        let subset2 = [shares[1].clone(), shares[3].clone(), shares[4].clone()];
+        assert_eq!(recover_decaps_key(&subset2).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_recover_rejects_duplicate_x() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(7);
+        let secret = [0xAAu8; 32];
+        let shares = split_decaps_key(&secret, 2, 4, &mut rng).unwrap();
+        let dup = [shares[0].clone(), shares[0].clone()];
+        assert!(recover_decaps_key(&dup).is_err());
+    }
+
+    #[test]
+    fn test_split_rejects_bad_params() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(7);
+        let secret = [0u8; 32];
+        assert!(split_decaps_key(&secret, 0, 4, &mut rng).is_err());
+        assert!(split_decaps_key(&secret, 5, 4, &mut rng).is_err());
+    }
+}
@@ -87,6 +87,32 @@ pub trait KeyGen {
     ) -> Result<(Self::EncapsKey, Self::DecapsKey), &'static str>;
 
 
+    /// Generates an encapsulation and decapsulation key pair from caller-supplied seeds rather
+    /// than an RNG. This is `ML-KEM.KeyGen_internal(d, z)` per FIPS 203, and exists so that
+    /// known-answer-test vectors and ACVP test groups (which fix the internal randomness) can
+    /// be reproduced bit-exactly; `try_keygen_with_rng()` is implemented in terms of this
+    /// function after drawing `d` and `z` from the RNG.
+    /// # Errors
+    /// Returns an error on an internal error condition.
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use fips203::ml_kem_512;                             // Could also be ml_kem_768 or ml_kem_1024.
+    /// use fips203::traits::KeyGen;
+    ///
+    /// let d = [0u8; 32];
+    /// let z = [1u8; 32];
+    /// let (ek, dk) = ml_kem_512::KG::keygen_internal(&d, &z)?;
+    /// # let _ = (ek, dk);
+    /// # Ok(())}
+    /// ```
+    #[cfg(feature = "deterministic")]
+    fn keygen_internal(
+        d: &[u8; 32], z: &[u8; 32],
+    ) -> Result<(Self::EncapsKey, Self::DecapsKey), &'static str>;
+
+
     /// Performs validation between an encapsulation key and a decapsulation key (both in byte arrays), perhaps in the
     /// scenario where both have been serialized, stored to disk, and then retrieved. This function is not intended
     /// to operate in constant-time.
@@ -186,6 +212,33 @@ pub trait Encaps {
     fn try_encaps_with_rng(
         &self, rng: &mut impl CryptoRngCore,
     ) -> Result<(Self::SharedSecretKey, Self::CipherText), &'static str>;
+
+
+    /// Generates a shared secret and ciphertext from an encapsulation key using a caller-supplied
+    /// message rather than an RNG. This is `ML-KEM.Encaps_internal(ek, m)` per FIPS 203, and
+    /// exists so that known-answer-test vectors and ACVP test groups (which fix the internal
+    /// randomness) can be reproduced bit-exactly; `try_encaps_with_rng()` is implemented in terms
+    /// of this function after drawing `m` from the RNG.
+    /// # Errors
+    /// Returns an error on an internal error condition.
+    /// # Examples
+    /// ```rust
+    /// # use std::error::Error;
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// use rand_core::OsRng;
+    /// use fips203::ml_kem_512;                             // Could also be ml_kem_768 or ml_kem_1024.
+    /// use fips203::traits::{KeyGen, Encaps};
+    ///
+    /// let (ek, _dk) = ml_kem_512::KG::try_keygen_with_rng(&mut OsRng)?;
+    /// let m = [0u8; 32];
+    /// let (ssk, ct) = ek.encaps_deterministic(&m)?;
+    /// # let _ = (ssk, ct);
+    /// # Ok(())}
+    /// ```
+    #[cfg(feature = "deterministic")]
+    fn encaps_deterministic(
+        &self, m: &[u8; 32],
+    ) -> Result<(Self::SharedSecretKey, Self::CipherText), &'static str>;
 }
 
 
@@ -20,14 +20,29 @@ pub struct DecapsKey<const DK_LEN: usize>(pub(crate) [u8; DK_LEN]);
 pub struct CipherText<const CT_LEN: usize>(pub(crate) [u8; CT_LEN]);
 
 
+/// A size-`K` batch of 256-coefficient polynomials -- one row or column of the `A_hat` matrix, or
+/// a full secret/error/ciphertext vector. Exists so [`crate::helpers::mul_mat_vec`] and its two
+/// siblings can name "a vector of `K` polynomials" once instead of repeating `[[Z; 256]; K]` at
+/// every call site; see those functions for the accumulator layout this enables.
+pub(crate) type PolyVec<const K: usize> = [[Z; 256]; K];
+
+
 // While Z is simple and correct, the performance is somewhat suboptimal.
 // This will be addressed (particularly in matrix operations etc) over
 // the medium-term - potentially using 256-entry rows.
 
 /// Stored as u16 for space, but arithmetic as u32 for perf
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Copy, Default, Zeroize)]
 pub(crate) struct Z(u16);
 
+// `to_montgomery`/`from_montgomery`/`mul_montgomery` below are what `ntt.rs`'s butterflies and
+// `base_case_multiply` actually multiply with now: `ntt`/`ntt_inv`/`multiply_ntts` convert their
+// input into Montgomery form once at entry, do every butterfly/base-case multiply via
+// `mul_montgomery` against a `ZETA_TABLE` that's itself precomputed in Montgomery form at compile
+// time, and convert back out via `from_montgomery` once at exit -- so the old per-multiply
+// Barrett reduction (`Self::mul`) no longer has a caller and is gone; `montgomery_reduce` plays
+// that role for every multiply in the hot path instead.
+
 
 #[allow(clippy::inline_always)]
 impl Z {
@@ -60,16 +75,76 @@ impl Z {
         Self(res as u16)
     }
 
+    /// `a * R^{-1} mod Q`, for `R = 2^16` and any `a < Q * R`. The standard Montgomery REDC step
+    /// using the negated inverse `QINV = -Q^{-1} mod 2^16`: `m = (a mod 2^16) * QINV mod 2^16` is
+    /// constructed so that `a + m*Q` is an exact multiple of `2^16` (since `m*Q ≡ -a mod 2^16`),
+    /// so `(a + m*Q) >> 16` divides out `R` with an add instead of Kyber's reference subtract.
+    /// The sum fits in `u32` for any `a < Q * R` (it's `< 2*Q*R`), and the shifted result lands
+    /// in `[0, 2*Q)`, so a single conditional subtraction (the mask trick already used by
+    /// [`Self::add`]/[`Self::sub`]) brings it into `[0, Q)` without a data-dependent branch.
+    #[inline(always)]
+    #[allow(clippy::cast_possible_truncation)]
+    fn montgomery_reduce(a: u32) -> Self {
+        /// `-Q^{-1} mod 2^16` (`Q^{-1} mod 2^16 = 62209`, negated and truncated to `u16`).
+        const QINV: u16 = 3327;
+        let m = (a as u16).wrapping_mul(QINV);
+        let t = (a + u32::from(m) * u32::from(Q)) >> 16;
+        let t = t.wrapping_sub(u32::from(Q));
+        let t = t.wrapping_add((t >> 16) & u32::from(Q));
+        debug_assert!(t < u32::from(Q));
+        Self(t as u16)
+    }
+
+    /// Converts `self` (an ordinary value `0 <= self < Q`) into Montgomery form, `self * R mod Q`
+    /// for `R = 2^16`. The old Barrett-reduction `mul`'s constant was only precise for products
+    /// of two values `< Q` (up to ~`Q^2`); `self * R` is up to ~`Q * 2^16`, over 16x larger, so
+    /// this uses its own wider-shift Barrett constant.
     #[inline(always)]
-    #[allow(clippy::items_after_statements, clippy::cast_possible_truncation)] // rem as u16; for perf
-    pub(crate) fn mul(self, other: Self) -> Self {
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn to_montgomery(self) -> Self {
         debug_assert!(self.0 < Q);
-        debug_assert!(other.0 < Q);
-        const M: u64 = ((1u64 << 36) + Q as u64 - 1) / Q as u64;
-        let prod = u32::from(self.0) * u32::from(other.0); // * debug=strict, release=wrapping
-        let quot = ((u64::from(prod) * M) >> 36) as u32;
-        let rem = prod - quot * u32::from(Q); // further reduction is not needed
+        const SHIFT: u32 = 48;
+        const M: u64 = (1u64 << SHIFT) / Q as u64 + 1;
+        let prod = u32::from(self.0) << 16; // self * R, R = 2^16
+        let quot = ((u64::from(prod) * M) >> SHIFT) as u32;
+        let rem = prod - quot * u32::from(Q);
         debug_assert!(rem < u32::from(Q));
         Self(rem as u16)
     }
+
+    /// Converts `self` out of Montgomery form, back to an ordinary value `0 <= self < Q`.
+    #[inline(always)]
+    pub(crate) fn from_montgomery(self) -> Self { Self::montgomery_reduce(self.get_u32()) }
+
+    /// Multiplies two Montgomery-form values, returning their product still in Montgomery form:
+    /// `(a * R) * (b * R) * R^{-1} = (a * b) * R mod Q`. One `montgomery_reduce` call is the hot
+    /// multiply behind every butterfly and base-case multiply in `ntt.rs`.
+    #[inline(always)]
+    pub(crate) fn mul_montgomery(self, other: Self) -> Self {
+        debug_assert!(self.0 < Q);
+        debug_assert!(other.0 < Q);
+        Self::montgomery_reduce(self.get_u32() * other.get_u32())
+    }
+
+    /// Folds a widened accumulator -- the sum of up to four already-reduced `Z` values (`K` is at
+    /// most 4, for ML-KEM-1024) added together without reducing mod Q in between -- back into
+    /// `[0, Q)`. Lets [`crate::helpers::mul_mat_vec`] and its two siblings pay one reduction per
+    /// output coefficient for the whole `K`-term row/column sum, instead of one reduction per
+    /// [`Self::add`] call as `K` grows.
+    ///
+    /// `sum < K * Q <= 4 * Q`, so at most three applications of the same conditional-subtract-Q
+    /// mask trick [`Self::add`]/[`Self::sub`] use (branchless, and correct regardless of how many
+    /// of those three subtractions are actually needed) bring it into range.
+    #[inline(always)]
+    #[allow(clippy::cast_possible_truncation)] // res as u16; for perf
+    pub(crate) fn reduce_wide(sum: u32) -> Self {
+        debug_assert!(sum < 4 * u32::from(Q));
+        let mut res = sum;
+        for _ in 0..3 {
+            res = res.wrapping_sub(u32::from(Q));
+            res = res.wrapping_add((res >> 16) & u32::from(Q));
+        }
+        debug_assert!(res < u32::from(Q));
+        Self(res as u16)
+    }
 }
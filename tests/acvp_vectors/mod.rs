@@ -0,0 +1,195 @@
+// ACVP vector driver for the official CAVP/ACVP ML-KEM `keyGen` and `encapDecap` JSON test
+// vector format (`prompt.json` + `expectedResults.json`), parsed generically via `serde_json`
+// rather than scraped field-by-field with regex (contrast `nist_vectors::mod`). Crucially this
+// exercises the ACVP "decapsulation failure" test cases: a deliberately malformed `c` is fed to
+// `try_decaps`, and the result must still match the published implicit-rejection `k` (derived
+// from the stored `z` and `c`) rather than erroring, since `try_decaps` has no failure branch to
+// take in the first place -- that is the one FIPS 203 code path `fails.rs`'s `fails_512` leaves
+// untested.
+//
+// Place the official vector files (named below) under `./tests/acvp_vectors/` to run this driver
+// against a given ACVP revision; no regex/field update is needed for a new revision, since every
+// field is read generically by name out of the parsed JSON.
+
+use std::fs;
+
+use hex::decode;
+use serde_json::Value;
+
+use fips203::traits::{Decaps, KeyGen, SerDes};
+use fips203::{ml_kem_1024, ml_kem_512, ml_kem_768};
+
+use super::TestRng;
+
+fn load(filename: &str) -> Value {
+    let data = fs::read_to_string(filename)
+        .unwrap_or_else(|e| panic!("Unable to read ACVP vector file {filename}: {e}"));
+    serde_json::from_str(&data).expect("Malformed ACVP JSON")
+}
+
+fn hex_field(test: &Value, field: &str) -> Vec<u8> {
+    let s = test[field].as_str().unwrap_or_else(|| panic!("missing field {field}"));
+    decode(s).unwrap_or_else(|e| panic!("bad hex in field {field}: {e}"))
+}
+
+/// Runs every `keyGen` test case in `prompt_file`/`results_file` for every test group whose
+/// `parameterSet` is `parameter_set`, via `keygen` (the parameter-set-specific deterministic
+/// keygen entry point), reporting the failing `tcId` on mismatch.
+fn run_keygen_vectors<EK: SerDes, DK: SerDes>(
+    prompt_file: &str, results_file: &str, parameter_set: &str,
+    keygen: impl Fn(&[u8], &[u8]) -> (EK, DK),
+) where
+    EK::ByteArray: AsRef<[u8]>,
+    DK::ByteArray: AsRef<[u8]>,
+{
+    let prompt = load(prompt_file);
+    let results = load(results_file);
+
+    for tg in prompt["testGroups"].as_array().unwrap() {
+        if tg["parameterSet"].as_str() != Some(parameter_set) {
+            continue;
+        }
+        let tg_id = tg["tgId"].as_u64().unwrap();
+        let result_tg = results["testGroups"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|g| g["tgId"].as_u64() == Some(tg_id))
+            .unwrap_or_else(|| panic!("no expectedResults testGroup for tgId {tg_id}"));
+
+        for test in tg["tests"].as_array().unwrap() {
+            let tc_id = test["tcId"].as_u64().unwrap();
+            let d = hex_field(test, "d");
+            let z = hex_field(test, "z");
+
+            let expected = result_tg["tests"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|t| t["tcId"].as_u64() == Some(tc_id))
+                .unwrap_or_else(|| panic!("no expectedResults test for tcId {tc_id}"));
+            let ek_exp = hex_field(expected, "ek");
+            let dk_exp = hex_field(expected, "dk");
+
+            let (ek_act, dk_act) = keygen(&d, &z);
+            assert_eq!(ek_exp, ek_act.into_bytes().as_ref(), "tcId {tc_id} (tgId {tg_id}): ek mismatch");
+            assert_eq!(dk_exp, dk_act.into_bytes().as_ref(), "tcId {tc_id} (tgId {tg_id}): dk mismatch");
+        }
+    }
+}
+
+/// Runs every `encapDecap` `decapsulation` test case (covering both `AFT` normal cases and `VAL`
+/// implicit-rejection/decapsulation-failure cases) in `prompt_file`/`results_file` for every test
+/// group whose `parameterSet` is `parameter_set`, reporting the failing `tcId` on mismatch.
+/// `try_decaps` never errors on a malformed `c` (implicit rejection instead), so `VAL` cases are
+/// checked the same way as `AFT` cases: `k` must match exactly.
+fn run_decaps_vectors<DK: SerDes, CT: SerDes>(
+    prompt_file: &str, results_file: &str, parameter_set: &str,
+    decaps: impl Fn(&DK, &CT) -> [u8; 32],
+) where
+    DK::ByteArray: TryFrom<Vec<u8>>,
+    CT::ByteArray: TryFrom<Vec<u8>>,
+{
+    let prompt = load(prompt_file);
+    let results = load(results_file);
+
+    for tg in prompt["testGroups"].as_array().unwrap() {
+        if tg["parameterSet"].as_str() != Some(parameter_set)
+            || tg["function"].as_str() != Some("decapsulation")
+        {
+            continue;
+        }
+        let tg_id = tg["tgId"].as_u64().unwrap();
+        let dk_bytes = hex_field(tg, "dk");
+        let dk = DK::try_from_bytes(
+            DK::ByteArray::try_from(dk_bytes).unwrap_or_else(|_| panic!("bad dk length, tgId {tg_id}")),
+        )
+        .unwrap_or_else(|e| panic!("tgId {tg_id}: dk rejected: {e}"));
+
+        let result_tg = results["testGroups"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|g| g["tgId"].as_u64() == Some(tg_id))
+            .unwrap_or_else(|| panic!("no expectedResults testGroup for tgId {tg_id}"));
+
+        for test in tg["tests"].as_array().unwrap() {
+            let tc_id = test["tcId"].as_u64().unwrap();
+            let c_bytes = hex_field(test, "c");
+            let ct = CT::try_from_bytes(
+                CT::ByteArray::try_from(c_bytes).unwrap_or_else(|_| panic!("bad c length, tcId {tc_id}")),
+            )
+            .unwrap_or_else(|e| panic!("tcId {tc_id}: c rejected: {e}"));
+
+            let expected = result_tg["tests"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|t| t["tcId"].as_u64() == Some(tc_id))
+                .unwrap_or_else(|| panic!("no expectedResults test for tcId {tc_id}"));
+            let k_exp = hex_field(expected, "k");
+
+            let k_act = decaps(&dk, &ct);
+            assert_eq!(k_exp, k_act, "tcId {tc_id} (tgId {tg_id}): k mismatch (implicit-rejection case included)");
+        }
+    }
+}
+
+#[test]
+fn test_acvp_keygen() {
+    run_keygen_vectors(
+        "./tests/acvp_vectors/ML-KEM-keyGen-prompt.json",
+        "./tests/acvp_vectors/ML-KEM-keyGen-expectedResults.json",
+        "ML-KEM-512",
+        |d, z| {
+            let mut rng = TestRng::new();
+            rng.push(d);
+            rng.push(z);
+            ml_kem_512::KG::try_keygen_with_rng(&mut rng).unwrap()
+        },
+    );
+    run_keygen_vectors(
+        "./tests/acvp_vectors/ML-KEM-keyGen-prompt.json",
+        "./tests/acvp_vectors/ML-KEM-keyGen-expectedResults.json",
+        "ML-KEM-768",
+        |d, z| {
+            let mut rng = TestRng::new();
+            rng.push(d);
+            rng.push(z);
+            ml_kem_768::KG::try_keygen_with_rng(&mut rng).unwrap()
+        },
+    );
+    run_keygen_vectors(
+        "./tests/acvp_vectors/ML-KEM-keyGen-prompt.json",
+        "./tests/acvp_vectors/ML-KEM-keyGen-expectedResults.json",
+        "ML-KEM-1024",
+        |d, z| {
+            let mut rng = TestRng::new();
+            rng.push(d);
+            rng.push(z);
+            ml_kem_1024::KG::try_keygen_with_rng(&mut rng).unwrap()
+        },
+    );
+}
+
+#[test]
+fn test_acvp_decaps_including_implicit_rejection() {
+    run_decaps_vectors::<ml_kem_512::DecapsKey, ml_kem_512::CipherText>(
+        "./tests/acvp_vectors/ML-KEM-encapDecap-prompt.json",
+        "./tests/acvp_vectors/ML-KEM-encapDecap-expectedResults.json",
+        "ML-KEM-512",
+        |dk, ct| dk.try_decaps(ct).unwrap().into_bytes(),
+    );
+    run_decaps_vectors::<ml_kem_768::DecapsKey, ml_kem_768::CipherText>(
+        "./tests/acvp_vectors/ML-KEM-encapDecap-prompt.json",
+        "./tests/acvp_vectors/ML-KEM-encapDecap-expectedResults.json",
+        "ML-KEM-768",
+        |dk, ct| dk.try_decaps(ct).unwrap().into_bytes(),
+    );
+    run_decaps_vectors::<ml_kem_1024::DecapsKey, ml_kem_1024::CipherText>(
+        "./tests/acvp_vectors/ML-KEM-encapDecap-prompt.json",
+        "./tests/acvp_vectors/ML-KEM-encapDecap-expectedResults.json",
+        "ML-KEM-1024",
+        |dk, ct| dk.try_decaps(ct).unwrap().into_bytes(),
+    );
+}
@@ -2,6 +2,11 @@ use fips203::traits::{Decaps, Encaps, KeyGen, SerDes};
 use fips203::{ml_kem_1024, ml_kem_512, ml_kem_768};
 use rand_chacha::rand_core::SeedableRng;
 
+// These flows exercise the NTT/MultiplyNTTs kernel selected by `ntt_backend`. CI runs this file
+// once per `--features force-portable`/`force-sse2`/`force-avx2`/`force-neon` to confirm the
+// dispatch plumbing for each forced selector still reproduces the same keygen/encaps/decaps
+// results -- today every selector dispatches to the same portable kernel under the hood (see
+// `ntt_backend` for why), so this isn't yet comparing distinct vectorized implementations.
 
 #[test]
 fn test_expected_flow_512() {